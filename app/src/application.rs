@@ -1,15 +1,18 @@
 use crate::configuration::{DatabaseSettings, Settings};
 use data::sqlite_repository::SqliteRepository;
-use domain::{AdminUseCase, ReaderUseCase};
+use domain::{AdminUseCase, ReaderUseCase, SyntaxHighlighting};
 use sqlx::sqlite::SqlitePoolOptions;
 use sqlx::SqlitePool;
 use std::net::TcpListener;
+use std::sync::Arc;
 use std::time;
+use web::site::SiteSettings;
 use web::startup::ServerArguments;
 
 pub struct Application {
     port: u16,
     server: web::Server,
+    pub connection_pool: SqlitePool,
     pub repository: SqliteRepository,
     pub reader_use_case: web::Data<ReaderUseCase>,
     pub admin_use_case: web::Data<AdminUseCase>,
@@ -24,8 +27,21 @@ impl Application {
             .await?;
 
         let repository = SqliteRepository::new(connection_pool.clone());
-        let reader_use_case = web::Data::new(ReaderUseCase::new(repository.clone()));
-        let admin_use_case = web::Data::new(AdminUseCase::new(repository.clone()));
+        let reader_use_case = web::Data::new(ReaderUseCase::new(
+            repository.clone(),
+            config.application.post_order,
+        ));
+        let highlighting = Arc::new(SyntaxHighlighting::load(&config.highlighting)?);
+        let admin_use_case = web::Data::new(AdminUseCase::new(
+            repository.clone(),
+            config.markdown.clone(),
+            highlighting.clone(),
+        ));
+        // Posts from before excerpts, word counts and tables of contents were
+        // stored don't have them. Serving the posts without them beats not starting at all.
+        if let Err(e) = admin_use_case.backfill_rendered_posts().await {
+            tracing::error!("Failed to backfill rendered blog posts: {e:?}");
+        }
 
         let address = format!("{}:{}", config.application.host, config.application.port);
         let listener = TcpListener::bind(address)?;
@@ -35,12 +51,20 @@ impl Application {
             listener,
             reader_use_case: reader_use_case.clone(),
             admin_use_case: admin_use_case.clone(),
+            highlighting: web::Data::from(highlighting),
             hmac_secret: config.application.hmac_secret,
+            site: SiteSettings {
+                title: config.application.title,
+                base_url: config.application.base_url,
+                robots_txt: config.application.robots_txt,
+                markdown: config.markdown,
+            },
         })?;
 
         Ok(Self {
             port,
             server,
+            connection_pool,
             repository,
             reader_use_case,
             admin_use_case,
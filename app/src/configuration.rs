@@ -1,6 +1,6 @@
 use anyhow::{bail, Context};
 use config::Config;
-use domain::HmacSecret;
+use domain::{HighlightingSettings, HmacSecret, MarkdownSettings, PostOrder};
 use serde_aux::prelude::*;
 use sqlx::sqlite::SqliteConnectOptions;
 use sqlx::ConnectOptions;
@@ -10,6 +10,10 @@ use std::str::FromStr;
 pub struct Settings {
     pub application: ApplicationSettings,
     pub database: DatabaseSettings,
+    #[serde(default)]
+    pub markdown: MarkdownSettings,
+    #[serde(default)]
+    pub highlighting: HighlightingSettings,
 }
 
 #[derive(serde::Deserialize, Debug)]
@@ -17,8 +21,14 @@ pub struct ApplicationSettings {
     #[serde(deserialize_with = "deserialize_number_from_string")]
     pub port: u16,
     pub host: String,
+    pub title: String,
     pub base_url: String,
     pub hmac_secret: HmacSecret,
+    /// Replaces the default robots.txt
+    pub robots_txt: Option<String>,
+    /// The order of posts on listing pages
+    #[serde(default)]
+    pub post_order: PostOrder,
 }
 
 #[derive(serde::Deserialize, Debug)]
@@ -59,7 +69,13 @@ impl Settings {
                     .separator("__"),
             );
 
-        Ok(config.build()?.try_deserialize()?)
+        let mut settings: Settings = config.build()?.try_deserialize()?;
+        if let Some(directory) = &mut settings.highlighting.directory {
+            if directory.is_relative() {
+                *directory = configuration_dir.join("..").join(&directory);
+            }
+        }
+        Ok(settings)
     }
 }
 
@@ -0,0 +1,125 @@
+use crate::helpers::spawn_app;
+
+#[tokio::test]
+async fn login_redirects_to_the_dashboard() {
+    // Arrange
+    let app = spawn_app().await;
+
+    // Act
+    let response = app.login().await;
+
+    // Assert
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(response.url().path(), "/admin/posts");
+}
+
+#[tokio::test]
+async fn dashboard_lists_posts_filtered_by_status() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=draft-post&title=A+draft&markdown=__rad__",
+    )
+    .await;
+    app.post(
+        "admin/new_post",
+        "url_id=published-post&title=A+published+post&markdown=__rad__&publish=true",
+    )
+    .await;
+    app.post(
+        "admin/new_post",
+        "url_id=trashed-post&title=A+trashed+post&markdown=__rad__&publish=true",
+    )
+    .await;
+    app.post("admin/posts/trashed-post/trash", "").await;
+
+    // Act
+    let all = app.get("admin/posts").await.text().await.unwrap();
+    let drafts = app
+        .get("admin/posts?status=draft")
+        .await
+        .text()
+        .await
+        .unwrap();
+    let published = app
+        .get("admin/posts?status=published")
+        .await
+        .text()
+        .await
+        .unwrap();
+
+    // Assert
+    assert!(all.contains("A draft"));
+    assert!(all.contains("A published post"));
+    assert!(!all.contains("A trashed post"));
+
+    assert!(drafts.contains("A draft"));
+    assert!(!drafts.contains("A published post"));
+
+    assert!(published.contains("A published post"));
+    assert!(published.contains("/admin/edit/published-post"));
+    assert!(!published.contains("A draft"));
+}
+
+#[tokio::test]
+async fn dashboard_lists_drafts_then_the_newest_publish_date_first() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=scheduled&title=Scheduled&markdown=a&publish=true&publish_at=2999-01-01T00%3A00",
+    )
+    .await;
+    app.post(
+        "admin/new_post",
+        "url_id=new&title=New&markdown=a&publish=true&publish_at=2024-03-01T00%3A00",
+    )
+    .await;
+    app.post(
+        "admin/new_post",
+        "url_id=old&title=Old&markdown=a&publish=true&publish_at=2020-03-01T00%3A00",
+    )
+    .await;
+    app.post("admin/new_post", "url_id=draft&title=Draft&markdown=a")
+        .await;
+    app.post(
+        "admin/edit/new",
+        "url_id=new&title=New&markdown=b&publish=true&publish_at=2019-03-01T00%3A00",
+    )
+    .await;
+
+    // Act
+    let posts = app.admin_use_case.get_posts(None).await.unwrap();
+
+    // Assert
+    let url_ids = posts.iter().map(|p| p.url_id.as_str()).collect::<Vec<_>>();
+    assert_eq!(url_ids, ["draft", "scheduled", "old", "new"]);
+}
+
+#[tokio::test]
+async fn dashboard_rejects_unknown_status_filter() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+
+    // Act
+    let response = app.get("admin/posts?status=whatever").await;
+
+    // Assert
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn dashboard_returns_a_401_when_not_authenticated() {
+    // Arrange
+    let app = spawn_app().await;
+
+    // Act
+    let response = app.get("admin/posts").await;
+
+    // Assert
+    assert_eq!(response.status().as_u16(), 401);
+}
@@ -0,0 +1,111 @@
+use crate::helpers::{spawn_app, TestApp};
+
+async fn create_post(app: &TestApp, url_id: &str, publish_at: &str) {
+    app.post(
+        "admin/new_post",
+        &format!(
+            "url_id={url_id}&title={url_id}&markdown=text&publish=true&publish_at={publish_at}"
+        ),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn archive_counts_published_posts_per_year_and_month() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    create_post(&app, "first", "2023-03-05T10:00").await;
+    create_post(&app, "second", "2023-03-20T10:00").await;
+    create_post(&app, "third", "2023-11-01T10:00").await;
+    create_post(&app, "fourth", "2024-01-15T10:00").await;
+    app.post("admin/new_post", "url_id=draft&title=draft&markdown=text")
+        .await;
+
+    // Act
+    let archive = app.reader_use_case.get_archive().await.unwrap();
+
+    // Assert
+    let counts = archive
+        .iter()
+        .map(|year| {
+            let months = year
+                .months
+                .iter()
+                .map(|month| (month.month, month.post_count))
+                .collect::<Vec<_>>();
+            (year.year, year.post_count, months)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        counts,
+        vec![(2024, 1, vec![(1, 1)]), (2023, 3, vec![(11, 1), (3, 2)]),]
+    );
+
+    let page = app.get("archive").await.text().await.unwrap();
+    assert!(page.contains(r#"href="/archive/2023/3">March</a> (2)"#));
+}
+
+#[tokio::test]
+async fn archive_year_page_lists_posts_of_that_year() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    create_post(&app, "old", "2022-12-31T23:00").await;
+    create_post(&app, "new", "2023-01-01T00:00").await;
+
+    // Act
+    let page = app.get("archive/2023").await.text().await.unwrap();
+
+    // Assert
+    assert!(page.contains("/blog/new"));
+    assert!(!page.contains("/blog/old"));
+    assert!(page.contains(r#"href="/archive/2023/1">January</a> (1)"#));
+}
+
+#[tokio::test]
+async fn archive_month_page_lists_posts_of_that_month() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    create_post(&app, "november", "2023-11-30T12:00").await;
+    create_post(&app, "december", "2023-12-01T12:00").await;
+
+    // Act
+    let page = app.get("archive/2023/12").await.text().await.unwrap();
+
+    // Assert
+    assert!(page.contains("December 2023"));
+    assert!(page.contains("/blog/december"));
+    assert!(!page.contains("/blog/november"));
+}
+
+#[tokio::test]
+async fn archive_month_page_rejects_invalid_month() {
+    // Arrange
+    let app = spawn_app().await;
+
+    // Act
+    let response = app.get("archive/2023/13").await;
+
+    // Assert
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn archive_pages_reject_years_out_of_range() {
+    // Arrange
+    let app = spawn_app().await;
+
+    for path in [
+        "archive/2147483647",
+        "archive/2147483647/12",
+        "archive/-2147483648",
+    ] {
+        // Act
+        let response = app.get(path).await;
+
+        // Assert
+        assert_eq!(response.status().as_u16(), 400, "path: {path}");
+    }
+}
@@ -1,4 +1,5 @@
 use crate::helpers::spawn_app;
+use domain::{NewBlogPostData, RenderedPost, Repository, TocEntry};
 
 #[tokio::test]
 async fn new_post_returns_a_200_for_valid_form_data() {
@@ -85,7 +86,7 @@ async fn test_error_message_information() {
 
     // Act
     let err_resp = reqwest::Client::new()
-        .get(&format!("{}/blow_up", app.address))
+        .get(format!("{}/blow_up", app.address))
         .send()
         .await
         .expect("Failed to execute request.");
@@ -93,3 +94,538 @@ async fn test_error_message_information() {
     // Assert
     assert_eq!(err_resp.status().as_u16(), 500);
 }
+
+#[tokio::test]
+async fn edit_post_updates_the_existing_blog_post() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=new-post&title=first&markdown=__rad__",
+    )
+    .await;
+
+    // Act
+    let response = app
+        .post(
+            "admin/edit/new-post",
+            "url_id=edited-post&title=second&markdown=__edited__&publish=true",
+        )
+        .await;
+
+    // Assert
+    assert_eq!(response.status().as_u16(), 200);
+
+    let posts = app
+        .admin_use_case
+        .get_all_posts()
+        .await
+        .expect("Error fetching blog posts");
+
+    assert_eq!(posts.len(), 1, "expected only one blog post");
+    assert_eq!(posts[0].url_id, "edited-post");
+    assert_eq!(posts[0].title, "second");
+    assert_eq!(posts[0].markdown, "__edited__");
+    assert!(posts[0].html.contains("<strong>edited</strong>"));
+    assert!(posts[0].updated_at.is_some());
+
+    let old = app
+        .reader_use_case
+        .get_post_by_url_id("new-post")
+        .await
+        .expect("Error fetching blog post");
+    assert!(old.is_none(), "the old url id should no longer resolve");
+}
+
+#[tokio::test]
+async fn edit_post_returns_a_401_when_not_authenticated() {
+    // Arrange
+    let app = spawn_app().await;
+
+    // Act
+    let page = app.get("admin/edit/new-post").await;
+    let response = app
+        .post(
+            "admin/edit/new-post",
+            "url_id=new-post&title=first&markdown=__rad__",
+        )
+        .await;
+
+    // Assert
+    assert_eq!(page.status().as_u16(), 401);
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn editing_non_existing_post_returns_404() {
+    // Arrange
+    let app = spawn_app().await;
+
+    // Act
+    app.login().await;
+    let page = app.get("admin/edit/does-not-exist").await;
+    let response = app
+        .post(
+            "admin/edit/does-not-exist",
+            "url_id=new-post&title=first&markdown=__rad__",
+        )
+        .await;
+
+    // Assert
+    assert_eq!(page.status().as_u16(), 404);
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn editing_post_to_a_url_id_in_use_returns_400() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post("admin/new_post", "url_id=first&title=first&markdown=one")
+        .await;
+    app.post("admin/new_post", "url_id=second&title=second&markdown=two")
+        .await;
+
+    // Act
+    let response = app
+        .post(
+            "admin/edit/second",
+            "url_id=first&title=second&markdown=two",
+        )
+        .await;
+
+    // Assert
+    assert_eq!(response.status().as_u16(), 400);
+    let post = app
+        .admin_use_case
+        .get_post_by_url_id("second")
+        .await
+        .expect("Error fetching blog post")
+        .expect("the post should keep its url id");
+    assert_eq!(post.markdown, "two");
+}
+
+#[tokio::test]
+async fn new_post_with_a_url_id_in_use_returns_400() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post("admin/new_post", "url_id=first&title=first&markdown=one")
+        .await;
+
+    // Act
+    let response = app
+        .post(
+            "admin/new_post",
+            "url_id=other&title=other&markdown=%2B%2B%2B%0Aurl_id+%3D+%22first%22%0A%2B%2B%2B%0Atwo",
+        )
+        .await;
+
+    // Assert
+    assert_eq!(response.status().as_u16(), 400);
+    let posts = app
+        .admin_use_case
+        .get_all_posts()
+        .await
+        .expect("Error fetching blog posts");
+    assert_eq!(posts.len(), 1);
+}
+
+#[tokio::test]
+async fn post_page_has_canonical_and_social_meta_tags() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=new-post&title=first&publish=true&markdown=An+*intro*+text%0A%0A%21%5Bcover%5D%28%2Fimages%2Fcover.png%29",
+    )
+    .await;
+
+    // Act
+    let page = app.get("blog/new-post").await.text().await.unwrap();
+
+    // Assert
+    assert!(page.contains(r#"<link rel="canonical" href="http://127.0.0.1/blog/new-post">"#));
+    assert!(page.contains(r#"<meta property="og:title" content="first">"#));
+    assert!(page.contains(r#"<meta property="og:description" content="An intro text">"#));
+    assert!(
+        page.contains(r#"<meta property="og:image" content="http://127.0.0.1/images/cover.png">"#)
+    );
+    assert!(page.contains(r#"<meta name="twitter:card" content="summary_large_image">"#));
+    assert!(page.contains(r#"<meta property="article:published_time""#));
+}
+
+#[tokio::test]
+async fn relative_cover_image_is_resolved_against_the_post_url() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=new-post&title=first&publish=true&markdown=%21%5Bcover%5D%28images%2Fcover.png%29",
+    )
+    .await;
+
+    // Act
+    let page = app.get("blog/new-post").await.text().await.unwrap();
+
+    // Assert
+    assert!(page.contains(
+        r#"<meta property="og:image" content="http://127.0.0.1/blog/images/cover.png">"#
+    ));
+    assert!(page.contains(
+        r#"<meta name="twitter:image" content="http://127.0.0.1/blog/images/cover.png">"#
+    ));
+}
+
+#[tokio::test]
+async fn new_post_stores_word_count_without_code_blocks() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+
+    // Act
+    app.post(
+        "admin/new_post",
+        "url_id=new-post&title=first&publish=true&markdown=Three+little+words%0A%0A%60%60%60%0Anot+counted%0A%60%60%60",
+    )
+    .await;
+
+    // Assert
+    let post = app
+        .reader_use_case
+        .get_post_by_url_id("new-post")
+        .await
+        .unwrap()
+        .expect("post should exist");
+    assert_eq!(post.word_count, 3);
+
+    let page = app.get("blog/new-post").await.text().await.unwrap();
+    assert!(page.contains("3 words, 1 min read"));
+}
+
+#[tokio::test]
+async fn post_with_toc_marker_shows_table_of_contents() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+
+    // Act
+    app.post(
+        "admin/new_post",
+        "url_id=new-post&title=first&publish=true&markdown=%5BTOC%5D%0A%0A%23+Getting+started%0A%0Atext",
+    )
+    .await;
+
+    // Assert
+    let page = app.get("blog/new-post").await.text().await.unwrap();
+    assert!(page.contains(
+        r##"<li class="toc-level-1"><a href="#getting-started">Getting started</a></li>"##
+    ));
+    assert!(page.contains(r#"<h1 id="getting-started">"#));
+    assert!(!page.contains("[TOC]"));
+}
+
+#[tokio::test]
+async fn new_post_takes_fields_from_front_matter() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    let markdown = "---%0Atitle%3A+From+front+matter%0Atags%3A+%5Brust%2C+web%5D%0A\
+        date%3A+2024-03-01%0Asummary%3A+Short.%0A---%0A%0A__rad__";
+
+    // Act
+    let response = app
+        .post(
+            "admin/new_post",
+            &format!("url_id=&title=&publish=true&markdown={markdown}"),
+        )
+        .await;
+
+    // Assert
+    assert_eq!(response.url().path(), "/blog/from-front-matter");
+    let post = app
+        .reader_use_case
+        .get_post_by_url_id("from-front-matter")
+        .await
+        .unwrap()
+        .expect("post should exist");
+    assert_eq!(post.title, "From front matter");
+    assert_eq!(post.tags, vec!["rust", "web"]);
+    assert_eq!(post.summary.as_deref(), Some("Short."));
+    assert_eq!(
+        post.published_at().unwrap().to_rfc3339(),
+        "2024-03-01T00:00:00+00:00"
+    );
+    assert_eq!(post.markdown, "__rad__");
+    assert_eq!(post.html, "<p><strong>rad</strong></p>\n");
+}
+
+#[tokio::test]
+async fn new_post_with_invalid_front_matter_returns_400() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+
+    // Act
+    let response = app
+        .post(
+            "admin/new_post",
+            "url_id=post&title=post&markdown=%2B%2B%2B%0Atitle+%3D%0A%2B%2B%2B%0Atext",
+        )
+        .await;
+
+    // Assert
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn stored_markdown_is_not_stripped_of_front_matter_again() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    let markdown = "---%0Atitle%3A+Post%0A---%0A---%0ANote%3A+x%0A---%0AText";
+
+    // Act
+    app.post(
+        "admin/new_post",
+        &format!("url_id=post&title=&publish=true&markdown={markdown}"),
+    )
+    .await;
+    app.post("admin/posts/rerender", "").await;
+
+    // Assert
+    let post = app
+        .reader_use_case
+        .get_post_by_url_id("post")
+        .await
+        .unwrap()
+        .expect("post should exist");
+    assert_eq!(post.markdown, "---\nNote: x\n---\nText");
+    assert!(post.html.contains("Note: x"));
+}
+
+#[tokio::test]
+async fn preview_leaves_out_the_front_matter() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+
+    // Act
+    let preview = app
+        .post(
+            "admin/preview_html",
+            "markdown=---%0Atitle%3A+Post%0A---%0A__rad__",
+        )
+        .await;
+    let invalid = app
+        .post(
+            "admin/preview_html",
+            "markdown=---%0Adraft%3A+maybe%0A---%0A__rad__",
+        )
+        .await;
+
+    // Assert
+    assert_eq!(
+        preview.text().await.unwrap(),
+        "<p><strong>rad</strong></p>\n"
+    );
+    assert_eq!(invalid.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn rerendering_posts_replaces_stale_html() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    let post: NewBlogPostData = serde_json::from_str(
+        r#"{"url_id": "old-post", "title": "Old", "markdown": "~~old~~", "publish": true}"#,
+    )
+    .unwrap();
+    let stale = RenderedPost {
+        html: "<p>~~old~~</p>".to_string(),
+        excerpt_html: String::new(),
+        word_count: 0,
+        toc: None,
+    };
+    app.repository.store_blog_post(&post, &stale).await.unwrap();
+
+    // Act
+    app.post("admin/posts/rerender", "").await;
+
+    // Assert
+    let post = app
+        .reader_use_case
+        .get_post_by_url_id("old-post")
+        .await
+        .unwrap()
+        .expect("post should exist");
+    assert_eq!(post.html, "<p><del>old</del></p>\n");
+    assert_eq!(post.excerpt_html, "<p><del>old</del></p>\n");
+    assert_eq!(post.word_count, 1);
+}
+
+#[tokio::test]
+async fn rerendering_posts_replaces_stale_table_of_contents() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    let post: NewBlogPostData = serde_json::from_str(
+        r#"{"url_id": "old-post", "title": "Old", "markdown": "[TOC]\n\n# Intro", "publish": true}"#,
+    )
+    .unwrap();
+    let stale = RenderedPost {
+        html: r#"<h1 id="old-intro">Intro</h1>"#.to_string(),
+        excerpt_html: String::new(),
+        word_count: 1,
+        toc: Some(vec![TocEntry {
+            level: 1,
+            id: "old-intro".to_string(),
+            title: "Intro".to_string(),
+        }]),
+    };
+    app.repository.store_blog_post(&post, &stale).await.unwrap();
+    let page = app.get("blog/old-post").await.text().await.unwrap();
+    assert!(page.contains(r##"<a href="#old-intro">Intro</a>"##));
+
+    // Act
+    app.post("admin/posts/rerender", "").await;
+
+    // Assert
+    let page = app.get("blog/old-post").await.text().await.unwrap();
+    assert!(page.contains(r##"<a href="#intro">Intro</a>"##));
+    assert!(page.contains(r#"<h1 id="intro">"#));
+}
+
+#[tokio::test]
+async fn post_with_math_renders_mathml_and_errors() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+
+    // Act
+    let response = app
+        .post(
+            "admin/new_post",
+            "url_id=math&title=Math&publish=true&markdown=Area+%24x%5E2%24+and+%24%5Cfoo%24",
+        )
+        .await;
+
+    // Assert
+    assert_eq!(response.status().as_u16(), 200);
+    let page = app.get("blog/math").await.text().await.unwrap();
+    assert!(page.contains("<msup><mi>x</mi><mn>2</mn></msup>"));
+    assert!(page.contains(r#"<span class="math-error"><code>$\foo$</code>"#));
+}
+
+#[tokio::test]
+async fn post_with_admonition_renders_callout() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+
+    // Act
+    app.post(
+        "admin/new_post",
+        "url_id=callout&title=Callout&publish=true&markdown=%3E+%5B%21NOTE%5D%0A%3E+Read+this.",
+    )
+    .await;
+
+    // Assert
+    let page = app.get("blog/callout").await.text().await.unwrap();
+    assert!(page.contains(r#"<aside class="admonition admonition-note">"#));
+    assert!(page.contains(r#"<p class="admonition-title">Note</p>"#));
+    assert!(!page.contains("[!NOTE]"));
+
+    let css = app.get("static/base.css").await.text().await.unwrap();
+    assert!(css.contains(".admonition-title {"));
+}
+
+#[tokio::test]
+async fn code_block_info_string_adds_title_and_highlighted_lines() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    let markdown = "%60%60%60rust+title%3D%22main.rs%22+%7B2%7D%0Afn+main%28%29+%7B%0A++++run%28%29%3B%0A%7D%0A%60%60%60";
+
+    // Act
+    app.post(
+        "admin/new_post",
+        &format!("url_id=code&title=Code&publish=true&markdown={markdown}"),
+    )
+    .await;
+
+    // Assert
+    let page = app.get("blog/code").await.text().await.unwrap();
+    assert!(page.contains(r#"<figcaption class="code-title">main.rs</figcaption>"#));
+    assert!(page.contains(r#"<span class="line highlighted"><span class="source rust">"#));
+    assert!(page.contains(r#"<span class="storage type function rust">fn</span>"#));
+}
+
+#[tokio::test]
+async fn highlight_css_is_generated_from_the_configured_themes() {
+    // Arrange
+    let app = spawn_app().await;
+
+    // Act
+    let light = app.get("static/light.css").await.text().await.unwrap();
+    let dark = app.get("static/dark.css").await.text().await.unwrap();
+
+    // Assert
+    assert!(light.starts_with(
+        "@media (prefers-color-scheme: no-preference), (prefers-color-scheme: light) {"
+    ));
+    assert!(light.contains("theme \"GitHub\" generated by syntect"));
+    assert!(dark.starts_with("@media (prefers-color-scheme: dark) {"));
+    assert!(dark.contains("theme \"Solarized (dark)\" generated by syntect"));
+    // The edited themes from the highlighting directory
+    assert!(!light.contains("#63a35c"));
+    assert!(dark.contains("color: #71b6a2;"));
+}
+
+#[tokio::test]
+async fn rerendering_posts_skips_and_reports_posts_that_fail() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    let stale = RenderedPost {
+        html: "<p>~~old~~</p>".to_string(),
+        excerpt_html: String::new(),
+        word_count: 0,
+        toc: None,
+    };
+    for url_id in ["broken", "fine"] {
+        let post: NewBlogPostData = serde_json::from_str(&format!(
+            r#"{{"url_id": "{url_id}", "title": "Old", "markdown": "~~old~~", "publish": true}}"#
+        ))
+        .unwrap();
+        app.repository.store_blog_post(&post, &stale).await.unwrap();
+    }
+    sqlx::query(
+        "CREATE TRIGGER fail_update BEFORE UPDATE OF html ON posts WHEN OLD.url_id = 'broken' \
+         BEGIN SELECT RAISE(ABORT, 'broken'); END",
+    )
+    .execute(&app.connection_pool)
+    .await
+    .unwrap();
+
+    // Act
+    let page = app
+        .post("admin/posts/rerender", "")
+        .await
+        .text()
+        .await
+        .unwrap();
+
+    // Assert
+    assert!(page.contains("Failed to render posts: broken"));
+    let post = app
+        .reader_use_case
+        .get_post_by_url_id("fine")
+        .await
+        .unwrap()
+        .expect("post should exist");
+    assert_eq!(post.html, "<p><del>old</del></p>\n");
+}
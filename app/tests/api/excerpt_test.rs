@@ -0,0 +1,146 @@
+use crate::helpers::spawn_app;
+use domain::{NewBlogPostData, RenderedPost, Repository};
+
+#[tokio::test]
+async fn index_shows_excerpt_up_to_more_marker() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+
+    // Act
+    app.post(
+        "admin/new_post",
+        "url_id=post&title=Post&publish=true&markdown=Intro+__text__.%0A%0A%3C%21--+more+--%3E%0A%0AThe+rest.",
+    )
+    .await;
+
+    // Assert
+    let page = app.get("").await.text().await.unwrap();
+    assert!(page.contains("Intro <strong>text</strong>."));
+    assert!(!page.contains("The rest."));
+}
+
+#[tokio::test]
+async fn summary_replaces_excerpt_and_description() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+
+    // Act
+    app.post(
+        "admin/new_post",
+        "url_id=post&title=Post&publish=true&markdown=First+paragraph.&summary=A+*short*+summary.",
+    )
+    .await;
+
+    // Assert
+    let page = app.get("").await.text().await.unwrap();
+    assert!(page.contains("A <em>short</em> summary."));
+    assert!(!page.contains("First paragraph."));
+
+    let post_page = app.get("blog/post").await.text().await.unwrap();
+    assert!(post_page.contains(r#"<meta name="description" content="A short summary.">"#));
+
+    let feed = app.get("feed.atom").await.text().await.unwrap();
+    assert!(feed.contains(
+        "<summary type=\"html\">&lt;p&gt;A &lt;em&gt;short&lt;/em&gt; summary.&lt;/p&gt;"
+    ));
+}
+
+#[tokio::test]
+async fn posts_stored_without_excerpt_are_rendered() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=old-post&title=Old&markdown=Old+*words*&publish=true",
+    )
+    .await;
+    // Posts from before excerpts and word counts were stored have them NULL
+    sqlx::query("UPDATE posts SET excerpt_html = NULL, word_count = NULL")
+        .execute(&app.connection_pool)
+        .await
+        .unwrap();
+
+    // Act
+    let rendered = app.admin_use_case.backfill_rendered_posts().await.unwrap();
+
+    // Assert
+    assert_eq!(rendered, 1);
+    let post = app
+        .reader_use_case
+        .get_post_by_url_id("old-post")
+        .await
+        .unwrap()
+        .expect("post should exist");
+    assert_eq!(post.excerpt_html, "<p>Old <em>words</em></p>\n");
+    assert_eq!(post.word_count, 2);
+
+    let rendered_again = app.admin_use_case.backfill_rendered_posts().await.unwrap();
+    assert_eq!(rendered_again, 0);
+}
+
+#[tokio::test]
+async fn posts_without_words_or_excerpt_are_not_rendered_again() {
+    // Arrange
+    let app = spawn_app().await;
+    let post: NewBlogPostData = serde_json::from_str(
+        r#"{"url_id": "code", "title": "Code", "markdown": "```\nfn main() {}\n```", "publish": true}"#,
+    )
+    .unwrap();
+    let no_words = RenderedPost {
+        html: "<pre><code>fn main() {}\n</code></pre>\n".to_string(),
+        excerpt_html: String::new(),
+        word_count: 0,
+        toc: None,
+    };
+    app.repository
+        .store_blog_post(&post, &no_words)
+        .await
+        .unwrap();
+
+    // Act
+    let rendered = app.admin_use_case.backfill_rendered_posts().await.unwrap();
+
+    // Assert
+    assert_eq!(rendered, 0);
+}
+
+#[tokio::test]
+async fn posts_stored_without_table_of_contents_are_rendered() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=toc&title=Toc&publish=true&markdown=%5BTOC%5D%0A%0A%23+Getting+started",
+    )
+    .await;
+    app.post(
+        "admin/new_post",
+        "url_id=code&title=Code&publish=true&markdown=Write+%60%5BTOC%5D%60+first.",
+    )
+    .await;
+    // Posts from before tables of contents were stored have them NULL
+    sqlx::query("UPDATE posts SET toc = NULL")
+        .execute(&app.connection_pool)
+        .await
+        .unwrap();
+
+    // Act
+    let rendered = app.admin_use_case.backfill_rendered_posts().await.unwrap();
+
+    // Assert
+    assert_eq!(rendered, 1);
+    let post = app
+        .reader_use_case
+        .get_post_by_url_id("toc")
+        .await
+        .unwrap()
+        .expect("post should exist");
+    assert_eq!(post.toc.map(|toc| toc.len()), Some(1));
+
+    let rendered_again = app.admin_use_case.backfill_rendered_posts().await.unwrap();
+    assert_eq!(rendered_again, 0);
+}
@@ -0,0 +1,208 @@
+use crate::helpers::spawn_app;
+
+#[tokio::test]
+async fn atom_feed_contains_published_posts_with_absolute_urls() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=published&title=Published&markdown=__rad__&publish=true",
+    )
+    .await;
+    app.post(
+        "admin/new_post",
+        "url_id=draft&title=Draft&markdown=__rad__",
+    )
+    .await;
+
+    // Act
+    let response = app.get("feed.atom").await;
+
+    // Assert
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(
+        response.headers()["content-type"],
+        "application/atom+xml; charset=utf-8"
+    );
+    let body = response.text().await.unwrap();
+    assert!(body.contains("<id>http://127.0.0.1/blog/published</id>"));
+    assert!(body.contains("&lt;strong&gt;rad&lt;/strong&gt;"));
+    assert!(!body.contains("/blog/draft"));
+}
+
+#[tokio::test]
+async fn rss_feed_contains_published_posts() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=published&title=Published&markdown=__rad__&publish=true&publish_at=2024-03-01T12%3A00",
+    )
+    .await;
+
+    // Act
+    let response = app.get("feed.rss").await;
+
+    // Assert
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(
+        response.headers()["content-type"],
+        "application/rss+xml; charset=utf-8"
+    );
+    let body = response.text().await.unwrap();
+    assert!(body.contains("<link>http://127.0.0.1/blog/published</link>"));
+    assert!(body.contains("<pubDate>Fri, 1 Mar 2024 12:00:00 +0000</pubDate>"));
+}
+
+#[tokio::test]
+async fn tag_feed_contains_only_posts_with_the_tag() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=tagged&title=Tagged&markdown=__rad__&publish=true&tags=rust",
+    )
+    .await;
+    app.post(
+        "admin/new_post",
+        "url_id=other&title=Other&markdown=__rad__&publish=true&tags=go",
+    )
+    .await;
+
+    // Act
+    let atom = app.get("tags/rust/feed.atom").await.text().await.unwrap();
+    let rss = app.get("tags/rust/feed.rss").await.text().await.unwrap();
+
+    // Assert
+    for body in [atom, rss] {
+        assert!(body.contains("http://127.0.0.1/blog/tagged"));
+        assert!(!body.contains("http://127.0.0.1/blog/other"));
+    }
+}
+
+#[tokio::test]
+async fn tag_feed_urls_are_percent_encoded() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=tagged&title=Tagged&markdown=__rad__&publish=true&tags=c%23",
+    )
+    .await;
+
+    // Act
+    let atom = app.get("tags/c%23/feed.atom").await.text().await.unwrap();
+
+    // Assert
+    assert!(atom.contains(r#"href="http://127.0.0.1/tags/c%23/feed.atom""#));
+    assert!(atom
+        .contains(r#"<link rel="alternate" type="text/html" href="http://127.0.0.1/tags/c%23"/>"#));
+}
+
+#[tokio::test]
+async fn tag_feed_for_a_tag_without_published_posts_returns_404() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=draft&title=Draft&markdown=__rad__&tags=rust",
+    )
+    .await;
+
+    // Act
+    let atom = app.get("tags/rust/feed.atom").await;
+    let rss = app.get("tags/does-not-exist/feed.rss").await;
+
+    // Assert
+    assert_eq!(atom.status().as_u16(), 404);
+    assert_eq!(rss.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn feed_content_has_relative_urls_resolved_against_the_post_url() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=post&title=Post&publish=true&markdown=\
+         %5Bsibling%5D%28other%29+%21%5Bimage%5D%28%2Fimage.png%29+\
+         %5Bsection%5D%28%23part%29+%5Bexternal%5D%28https%3A%2F%2Fexample.com%29",
+    )
+    .await;
+
+    // Act
+    let atom = app.get("feed.atom").await.text().await.unwrap();
+    let rss = app.get("feed.rss").await.text().await.unwrap();
+
+    // Assert
+    for body in [atom, rss] {
+        assert!(body.contains("href=&quot;http://127.0.0.1/blog/other&quot;"));
+        assert!(body.contains("src=&quot;http://127.0.0.1/image.png&quot;"));
+        assert!(body.contains("href=&quot;http://127.0.0.1/blog/post#part&quot;"));
+        assert!(body.contains("href=&quot;https://example.com&quot;"));
+    }
+}
+
+#[tokio::test]
+async fn json_feed_follows_the_json_feed_spec() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=published&title=Published&markdown=__rad__&publish=true&publish_at=2024-03-01T12%3A00",
+    )
+    .await;
+
+    // Act
+    let response = app.get("feed.json").await;
+
+    // Assert
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(
+        response.headers()["content-type"],
+        "application/feed+json; charset=utf-8"
+    );
+    let feed: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(feed["version"], "https://jsonfeed.org/version/1.1");
+    assert_eq!(feed["feed_url"], "http://127.0.0.1/feed.json");
+
+    let item = &feed["items"][0];
+    assert_eq!(item["id"], "http://127.0.0.1/blog/published");
+    assert_eq!(item["url"], "http://127.0.0.1/blog/published");
+    assert_eq!(item["title"], "Published");
+    assert_eq!(item["content_html"], "<p><strong>rad</strong></p>\n");
+    assert_eq!(item["content_text"], "__rad__");
+    assert_eq!(item["date_published"], "2024-03-01T12:00:00+00:00");
+}
+
+#[tokio::test]
+async fn json_feed_contains_only_the_newest_posts_newest_first() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    for day in 1..=21 {
+        app.post(
+            "admin/new_post",
+            &format!(
+                "url_id=post-{day}&title=Post&markdown=text&publish=true&publish_at=2024-03-{day:02}T12%3A00"
+            ),
+        )
+        .await;
+    }
+
+    // Act
+    let feed: serde_json::Value = app.get("feed.json").await.json().await.unwrap();
+
+    // Assert
+    let items = feed["items"].as_array().unwrap();
+    assert_eq!(items.len(), 20);
+    assert_eq!(items[0]["url"], "http://127.0.0.1/blog/post-21");
+    assert_eq!(items[19]["url"], "http://127.0.0.1/blog/post-2");
+}
@@ -1,3 +1,4 @@
+use data::sqlite_repository::SqliteRepository;
 use domain::{AdminUseCase, Password, ReaderUseCase, Repository};
 use mimir::application::Application;
 use mimir::configuration::Settings;
@@ -5,8 +6,9 @@ use mimir::telemetry::init_subscriber;
 use once_cell::sync::Lazy;
 use reqwest::Client;
 use secrecy::Secret;
+use sqlx::SqlitePool;
 
-const ADMIN_PASSWORD: &'static str = "123456578";
+const ADMIN_PASSWORD: &str = "123456578";
 
 static TRACING: Lazy<()> = Lazy::new(|| {
     let subscriber_name = "test";
@@ -22,16 +24,17 @@ static TRACING: Lazy<()> = Lazy::new(|| {
 
 pub struct TestApp {
     pub address: String,
-    pub port: u16,
     pub reader_use_case: web::Data<ReaderUseCase>,
     pub admin_use_case: web::Data<AdminUseCase>,
+    pub repository: SqliteRepository,
+    pub connection_pool: SqlitePool,
     pub client: Client,
 }
 
 impl TestApp {
     pub async fn login(&self) -> reqwest::Response {
         self.client
-            .post(&format!("{}/login", self.address))
+            .post(format!("{}/login", self.address))
             .header("Content-Type", "application/x-www-form-urlencoded")
             .body(format!("password={}", ADMIN_PASSWORD))
             .send()
@@ -41,7 +44,7 @@ impl TestApp {
 
     pub async fn post(&self, endpoint: &str, body: &str) -> reqwest::Response {
         self.client
-            .post(&format!("{}/{}", self.address, endpoint))
+            .post(format!("{}/{}", self.address, endpoint))
             .header("Content-Type", "application/x-www-form-urlencoded")
             .body(body.to_string())
             .send()
@@ -51,7 +54,7 @@ impl TestApp {
 
     pub async fn get(&self, endpoint: &str) -> reqwest::Response {
         self.client
-            .get(&format!("{}/{}", self.address, endpoint))
+            .get(format!("{}/{}", self.address, endpoint))
             .send()
             .await
             .expect("Failed to execute request.")
@@ -89,15 +92,18 @@ pub async fn spawn_app() -> TestApp {
 
     let reader_use_case = application.reader_use_case.clone();
     let admin_use_case = application.admin_use_case.clone();
+    let repository = application.repository.clone();
+    let connection_pool = application.connection_pool.clone();
     let _handle = tokio::spawn(application.run_until_stopped());
 
     let client = Client::builder().cookie_store(true).build().unwrap();
 
     TestApp {
         address,
-        port: application_port,
         reader_use_case,
         admin_use_case,
+        repository,
+        connection_pool,
         client,
     }
 }
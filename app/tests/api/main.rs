@@ -1,3 +1,14 @@
+mod admin_dashboard_test;
+mod archive_test;
 mod blog_post_test;
+mod excerpt_test;
+mod feed_test;
 mod health_check_test;
 mod helpers;
+mod pagination_test;
+mod post_status_test;
+mod revisions_test;
+mod search_test;
+mod series_test;
+mod sitemap_test;
+mod tags_test;
@@ -0,0 +1,113 @@
+use crate::helpers::{spawn_app, TestApp};
+
+async fn create_posts(app: &TestApp, count: usize) {
+    for i in 1..=count {
+        app.post(
+            "admin/new_post",
+            &format!("url_id=post-{i}&title=Post+{i}&markdown=text&publish=true"),
+        )
+        .await;
+    }
+}
+
+#[tokio::test]
+async fn index_shows_first_page_with_next_link() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    create_posts(&app, 11).await;
+
+    // Act
+    let response = reqwest::Client::new()
+        .get(format!("{}/", app.address))
+        .send()
+        .await
+        .unwrap();
+
+    // Assert
+    let link = response.headers()["link"].to_str().unwrap().to_string();
+    assert_eq!(link, r#"<http://127.0.0.1/?page=2>; rel="next""#);
+    let page = response.text().await.unwrap();
+    assert!(page.contains(r#"href="/?page=2""#));
+    assert!(!page.contains(r#"rel="prev""#));
+    assert!(page.contains("/blog/post-11"));
+    assert!(!page.contains(r#"/blog/post-1""#));
+}
+
+#[tokio::test]
+async fn index_second_page_links_back_to_first() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    create_posts(&app, 11).await;
+
+    // Act
+    let response = reqwest::Client::new()
+        .get(format!("{}/?page=2", app.address))
+        .send()
+        .await
+        .unwrap();
+
+    // Assert
+    let link = response.headers()["link"].to_str().unwrap().to_string();
+    assert_eq!(link, r#"<http://127.0.0.1/>; rel="prev""#);
+    let page = response.text().await.unwrap();
+    assert!(page.contains(r#"/blog/post-1""#));
+    assert!(!page.contains("/blog/post-11"));
+}
+
+#[tokio::test]
+async fn index_page_past_the_end_is_not_found() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    create_posts(&app, 1).await;
+
+    // Act
+    let response = reqwest::Client::new()
+        .get(format!("{}/?page=2", app.address))
+        .send()
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn index_without_enough_posts_has_no_link_header() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    create_posts(&app, 2).await;
+
+    // Act
+    let response = reqwest::Client::new()
+        .get(format!("{}/", app.address))
+        .send()
+        .await
+        .unwrap();
+
+    // Assert
+    assert!(response.headers().get("link").is_none());
+}
+
+#[tokio::test]
+async fn index_lists_pinned_posts_first() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    create_posts(&app, 11).await;
+
+    // Act
+    app.post("admin/posts/post-1/pin", "").await;
+
+    // Assert
+    let page = app.get("").await.text().await.unwrap();
+    let pinned = page
+        .find(r#"/blog/post-1""#)
+        .expect("pinned post on first page");
+    let newest = page.find("/blog/post-11").unwrap();
+    assert!(pinned < newest);
+    assert!(page.contains(r#"class="pinned""#));
+}
@@ -0,0 +1,237 @@
+use crate::helpers::spawn_app;
+
+#[tokio::test]
+async fn unpublished_post_is_hidden_from_readers() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=new-post&title=first&markdown=__rad__&publish=true",
+    )
+    .await;
+
+    // Act
+    let response = app.post("admin/posts/new-post/unpublish", "").await;
+
+    // Assert
+    assert_eq!(response.status().as_u16(), 200);
+
+    let published = app.reader_use_case.get_published_posts().await.unwrap();
+    assert!(published.is_empty());
+
+    let post = app
+        .reader_use_case
+        .get_post_by_url_id("new-post")
+        .await
+        .unwrap();
+    assert!(post.is_none());
+
+    let anonymous = reqwest::Client::new()
+        .get(format!("{}/blog/new-post", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(anonymous.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn republishing_keeps_the_original_publish_date() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=new-post&title=first&markdown=__rad__&publish=true",
+    )
+    .await;
+    let original = app.reader_use_case.get_published_posts().await.unwrap()[0].published_at();
+
+    // Act
+    app.post("admin/posts/new-post/unpublish", "").await;
+    app.post("admin/posts/new-post/publish", "").await;
+
+    // Assert
+    let posts = app.reader_use_case.get_published_posts().await.unwrap();
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0].published_at(), original);
+}
+
+#[tokio::test]
+async fn trashed_post_is_hidden_and_can_be_restored() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=new-post&title=first&markdown=__rad__&publish=true",
+    )
+    .await;
+
+    // Act
+    app.post("admin/posts/new-post/trash", "").await;
+    let trashed = app.admin_use_case.get_trashed_posts().await.unwrap();
+    let published_while_trashed = app.reader_use_case.get_published_posts().await.unwrap();
+    let post_while_trashed = app
+        .reader_use_case
+        .get_post_by_url_id("new-post")
+        .await
+        .unwrap();
+
+    app.post("admin/posts/new-post/restore", "").await;
+
+    // Assert
+    assert_eq!(trashed.len(), 1);
+    assert!(published_while_trashed.is_empty());
+    assert!(post_while_trashed.is_none());
+
+    let published = app.reader_use_case.get_published_posts().await.unwrap();
+    assert_eq!(
+        published.len(),
+        1,
+        "restored post should be published again"
+    );
+    assert!(app
+        .admin_use_case
+        .get_trashed_posts()
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn editing_a_trashed_post_keeps_its_published_state() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=new-post&title=first&markdown=__rad__&publish=true",
+    )
+    .await;
+    app.post("admin/posts/new-post/trash", "").await;
+
+    // Act
+    app.post(
+        "admin/edit/new-post",
+        "url_id=new-post&title=second&markdown=__edited__",
+    )
+    .await;
+    let revision_id = app
+        .admin_use_case
+        .get_post_revisions("new-post")
+        .await
+        .unwrap()[0]
+        .id;
+    app.post(
+        &format!("admin/posts/new-post/revisions/{revision_id}/restore"),
+        "",
+    )
+    .await;
+    app.post("admin/posts/new-post/restore", "").await;
+
+    // Assert
+    let published = app.reader_use_case.get_published_posts().await.unwrap();
+    assert_eq!(
+        published.len(),
+        1,
+        "restored post should be published again"
+    );
+    assert_eq!(published[0].title, "first");
+}
+
+#[tokio::test]
+async fn only_trashed_posts_can_be_purged() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=new-post&title=first&markdown=__rad__&publish=true",
+    )
+    .await;
+
+    // Act
+    let rejected = app.post("admin/posts/new-post/purge", "").await;
+    app.post("admin/posts/new-post/trash", "").await;
+    let purged = app.post("admin/posts/new-post/purge", "").await;
+
+    // Assert
+    assert_eq!(rejected.status().as_u16(), 400);
+    assert_eq!(purged.status().as_u16(), 200);
+
+    let posts = app.admin_use_case.get_all_posts().await.unwrap();
+    assert!(posts.is_empty());
+}
+
+#[tokio::test]
+async fn post_status_actions_return_a_401_when_not_authenticated() {
+    // Arrange
+    let app = spawn_app().await;
+
+    for action in ["publish", "unpublish", "trash", "restore", "purge"] {
+        // Act
+        let response = app
+            .post(&format!("admin/posts/new-post/{action}"), "")
+            .await;
+
+        // Assert
+        assert_eq!(response.status().as_u16(), 401, "action: {action}");
+    }
+
+    let trash_page = app.get("admin/trash").await;
+    assert_eq!(trash_page.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn scheduled_post_is_hidden_until_its_publish_date() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+
+    // Act
+    app.post(
+        "admin/new_post",
+        "url_id=new-post&title=first&markdown=__rad__&publish=true&publish_at=2999-01-01T12%3A00",
+    )
+    .await;
+
+    // Assert
+    let published = app.reader_use_case.get_published_posts().await.unwrap();
+    assert!(published.is_empty());
+
+    let post = app
+        .reader_use_case
+        .get_post_by_url_id("new-post")
+        .await
+        .unwrap();
+    assert!(post.is_none());
+
+    let posts = app.admin_use_case.get_all_posts().await.unwrap();
+    assert!(posts[0].is_scheduled());
+
+    let admin_list = app.get("admin/posts").await.text().await.unwrap();
+    assert!(admin_list.contains("Scheduled for 2999-01-01 12:00:00 UTC"));
+}
+
+#[tokio::test]
+async fn post_with_past_publish_date_is_visible() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+
+    // Act
+    app.post(
+        "admin/new_post",
+        "url_id=new-post&title=first&markdown=__rad__&publish=true&publish_at=2020-01-01T12%3A00",
+    )
+    .await;
+
+    // Assert
+    let published = app.reader_use_case.get_published_posts().await.unwrap();
+    assert_eq!(published.len(), 1);
+    assert_eq!(
+        published[0].published_at().unwrap().to_rfc3339(),
+        "2020-01-01T12:00:00+00:00"
+    );
+}
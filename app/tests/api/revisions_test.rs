@@ -0,0 +1,161 @@
+use crate::helpers::spawn_app;
+
+#[tokio::test]
+async fn editing_a_post_keeps_the_previous_version_as_a_revision() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=new-post&title=first&markdown=__rad__",
+    )
+    .await;
+
+    // Act
+    app.post(
+        "admin/edit/new-post",
+        "url_id=new-post&title=second&markdown=__edited__",
+    )
+    .await;
+    // Saving without changes shouldn't create another revision
+    app.post(
+        "admin/edit/new-post",
+        "url_id=new-post&title=second&markdown=__edited__",
+    )
+    .await;
+
+    // Assert
+    let revisions = app
+        .admin_use_case
+        .get_post_revisions("new-post")
+        .await
+        .expect("Error fetching revisions");
+
+    assert_eq!(revisions.len(), 1);
+    assert_eq!(revisions[0].title, "first");
+    assert_eq!(revisions[0].markdown, "__rad__");
+}
+
+#[tokio::test]
+async fn restoring_a_revision_rolls_back_the_post() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=new-post&title=first&markdown=__rad__",
+    )
+    .await;
+    app.post(
+        "admin/edit/new-post",
+        "url_id=new-post&title=second&markdown=__edited__",
+    )
+    .await;
+    let revision_id = app
+        .admin_use_case
+        .get_post_revisions("new-post")
+        .await
+        .unwrap()[0]
+        .id;
+
+    // Act
+    let response = app
+        .post(
+            &format!("admin/posts/new-post/revisions/{revision_id}/restore"),
+            "",
+        )
+        .await;
+
+    // Assert
+    assert_eq!(response.status().as_u16(), 200);
+
+    let post = app
+        .admin_use_case
+        .get_post_by_url_id("new-post")
+        .await
+        .unwrap()
+        .expect("post should exist");
+    assert_eq!(post.title, "first");
+    assert_eq!(post.markdown, "__rad__");
+    assert!(post.html.contains("<strong>rad</strong>"));
+
+    let revisions = app
+        .admin_use_case
+        .get_post_revisions("new-post")
+        .await
+        .unwrap();
+    assert_eq!(revisions.len(), 2, "the restore should itself be undoable");
+    assert_eq!(revisions[0].markdown, "__edited__");
+}
+
+#[tokio::test]
+async fn revision_diff_page_shows_changed_lines() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=new-post&title=first&markdown=old+line",
+    )
+    .await;
+    app.post(
+        "admin/edit/new-post",
+        "url_id=new-post&title=first&markdown=new+line",
+    )
+    .await;
+    let revision_id = app
+        .admin_use_case
+        .get_post_revisions("new-post")
+        .await
+        .unwrap()[0]
+        .id;
+
+    // Act
+    let response = app
+        .get(&format!("admin/posts/new-post/revisions/{revision_id}"))
+        .await;
+
+    // Assert
+    assert_eq!(response.status().as_u16(), 200);
+    let body = response.text().await.unwrap();
+    assert!(body.contains("- old line"));
+    assert!(body.contains("+ new line"));
+}
+
+#[tokio::test]
+async fn non_existing_revision_returns_404() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=new-post&title=first&markdown=__rad__",
+    )
+    .await;
+
+    // Act
+    let diff = app.get("admin/posts/new-post/revisions/42").await;
+    let restore = app
+        .post("admin/posts/new-post/revisions/42/restore", "")
+        .await;
+
+    // Assert
+    assert_eq!(diff.status().as_u16(), 404);
+    assert_eq!(restore.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn revisions_return_a_401_when_not_authenticated() {
+    // Arrange
+    let app = spawn_app().await;
+
+    // Act
+    let list = app.get("admin/posts/new-post/revisions").await;
+    let restore = app
+        .post("admin/posts/new-post/revisions/1/restore", "")
+        .await;
+
+    // Assert
+    assert_eq!(list.status().as_u16(), 401);
+    assert_eq!(restore.status().as_u16(), 401);
+}
@@ -0,0 +1,149 @@
+use crate::helpers::spawn_app;
+
+#[tokio::test]
+async fn search_finds_published_posts_with_highlighted_snippets() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=ferris&title=Ferris&markdown=The+crab+is+called+ferris&publish=true",
+    )
+    .await;
+    app.post(
+        "admin/new_post",
+        "url_id=other&title=Other&markdown=Nothing+to+see&publish=true",
+    )
+    .await;
+
+    // Act
+    let results = app
+        .reader_use_case
+        .search_published_posts("crab")
+        .await
+        .unwrap();
+    let page = app.get("search?q=crab").await.text().await.unwrap();
+
+    // Assert
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].post.url_id, "ferris");
+    assert!(page.contains("<mark>crab</mark>"));
+    assert!(!page.contains("Other"));
+}
+
+#[tokio::test]
+async fn search_ranks_title_matches_first() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=body&title=Body&markdown=all+about+sqlite+and+more&publish=true",
+    )
+    .await;
+    app.post(
+        "admin/new_post",
+        "url_id=title&title=SQLite&markdown=a+post&publish=true",
+    )
+    .await;
+
+    // Act
+    let results = app
+        .reader_use_case
+        .search_published_posts("sqlite")
+        .await
+        .unwrap();
+
+    // Assert
+    let url_ids = results
+        .iter()
+        .map(|r| r.post.url_id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(url_ids, vec!["title", "body"]);
+}
+
+#[tokio::test]
+async fn search_ignores_unpublished_posts_and_follows_edits() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=draft&title=Draft&markdown=secret+plans",
+    )
+    .await;
+    app.post(
+        "admin/new_post",
+        "url_id=edited&title=Edited&markdown=old+words&publish=true",
+    )
+    .await;
+    app.post(
+        "admin/edit/edited",
+        "url_id=edited&title=Edited&markdown=new+words&publish=true",
+    )
+    .await;
+
+    // Act
+    let secret = app
+        .reader_use_case
+        .search_published_posts("secret")
+        .await
+        .unwrap();
+    let old = app
+        .reader_use_case
+        .search_published_posts("old")
+        .await
+        .unwrap();
+    let new = app
+        .reader_use_case
+        .search_published_posts("new")
+        .await
+        .unwrap();
+
+    // Assert
+    assert!(secret.is_empty());
+    assert!(old.is_empty());
+    assert_eq!(new.len(), 1);
+}
+
+#[tokio::test]
+async fn search_results_are_not_crowded_out_by_unpublished_posts() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    for i in 0..60 {
+        app.post(
+            "admin/new_post",
+            &format!("url_id=draft-{i}&title=Crab+{i}&markdown=crab+crab+crab"),
+        )
+        .await;
+    }
+    app.post(
+        "admin/new_post",
+        "url_id=published&title=Published&markdown=a+crab&publish=true",
+    )
+    .await;
+
+    // Act
+    let results = app
+        .reader_use_case
+        .search_published_posts("crab")
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].post.url_id, "published");
+}
+
+#[tokio::test]
+async fn search_handles_query_syntax_characters() {
+    // Arrange
+    let app = spawn_app().await;
+
+    // Act
+    let response = app.get("search?q=%22unbalanced+AND+(+*").await;
+
+    // Assert
+    assert_eq!(response.status().as_u16(), 200);
+}
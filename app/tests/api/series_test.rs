@@ -0,0 +1,161 @@
+use crate::helpers::spawn_app;
+
+#[tokio::test]
+async fn posts_are_added_to_the_end_of_a_series() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+
+    // Act
+    for url_id in ["part-one", "part-two"] {
+        app.post(
+            "admin/new_post",
+            &format!(
+                "url_id={url_id}&title={url_id}&markdown=__rad__&publish=true&series=Rust+Tutorial"
+            ),
+        )
+        .await;
+    }
+
+    // Assert
+    let series = app
+        .reader_use_case
+        .get_series("rust-tutorial")
+        .await
+        .unwrap()
+        .expect("series should exist");
+    assert_eq!(series.title, "Rust Tutorial");
+
+    let parts = series
+        .posts
+        .iter()
+        .map(|p| (p.url_id.as_str(), p.series.as_ref().unwrap().part))
+        .collect::<Vec<_>>();
+    assert_eq!(parts, vec![("part-one", 1), ("part-two", 2)]);
+}
+
+#[tokio::test]
+async fn post_page_shows_series_navigation() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    for (url_id, part) in [("second", 2), ("first", 1), ("third", 3)] {
+        app.post(
+            "admin/new_post",
+            &format!("url_id={url_id}&title=Title+{url_id}&markdown=__rad__&publish=true&series=Rust+Tutorial&series_part={part}"),
+        )
+        .await;
+    }
+
+    // Act
+    let page = app.get("blog/second").await.text().await.unwrap();
+
+    // Assert
+    assert!(page.contains("Part 2 of 3"));
+    assert!(page.contains(r#"<a href="/series/rust-tutorial">Rust Tutorial</a>"#));
+    assert!(page.contains(r#"<a href="/blog/first">Title first</a>"#));
+    assert!(page.contains(r#"<a href="/blog/third">Title third</a>"#));
+}
+
+#[tokio::test]
+async fn series_page_lists_only_published_parts() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=published&title=Published+part&markdown=__rad__&publish=true&series=Rust+Tutorial",
+    )
+    .await;
+    app.post(
+        "admin/new_post",
+        "url_id=draft&title=Draft+part&markdown=__rad__&series=Rust+Tutorial",
+    )
+    .await;
+
+    // Act
+    let page = app.get("series/rust-tutorial").await.text().await.unwrap();
+
+    // Assert
+    assert!(page.contains("Published part"));
+    assert!(!page.contains("Draft part"));
+}
+
+#[tokio::test]
+async fn removing_a_post_from_a_series_deletes_the_empty_series() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=new-post&title=first&markdown=__rad__&publish=true&series=Rust+Tutorial",
+    )
+    .await;
+
+    // Act
+    app.post(
+        "admin/edit/new-post",
+        "url_id=new-post&title=first&markdown=__rad__&publish=true&series=&series_part=",
+    )
+    .await;
+
+    // Assert
+    let response = app.get("series/rust-tutorial").await;
+    assert_eq!(response.status().as_u16(), 404);
+
+    let post = app
+        .reader_use_case
+        .get_post_by_url_id("new-post")
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(post.series.is_none());
+}
+
+#[tokio::test]
+async fn series_title_is_updated_when_renamed_under_the_same_url_id() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=new-post&title=first&markdown=__rad__&publish=true&series=Rust+tutorial",
+    )
+    .await;
+
+    // Act
+    app.post(
+        "admin/edit/new-post",
+        "url_id=new-post&title=first&markdown=__rad__&publish=true&series=Rust+Tutorial",
+    )
+    .await;
+
+    // Assert
+    let series = app
+        .reader_use_case
+        .get_series("rust-tutorial")
+        .await
+        .unwrap()
+        .expect("series should exist");
+    assert_eq!(series.title, "Rust Tutorial");
+}
+
+#[tokio::test]
+async fn series_title_without_ascii_letters_or_digits_is_rejected() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+
+    // Act
+    let response = app
+        .post(
+            "admin/new_post",
+            "url_id=new-post&title=first&markdown=__rad__&publish=true&series=%E6%97%A5%E6%9C%AC",
+        )
+        .await;
+
+    // Assert
+    assert_eq!(response.status().as_u16(), 400);
+    let posts = app.admin_use_case.get_all_posts().await.unwrap();
+    assert!(posts.is_empty());
+}
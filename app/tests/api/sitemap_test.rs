@@ -0,0 +1,44 @@
+use crate::helpers::spawn_app;
+
+#[tokio::test]
+async fn sitemap_lists_index_and_published_posts() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=published&title=Published&markdown=__rad__&publish=true&publish_at=2024-03-01T12%3A00",
+    )
+    .await;
+    app.post(
+        "admin/new_post",
+        "url_id=draft&title=Draft&markdown=__rad__",
+    )
+    .await;
+
+    // Act
+    let response = app.get("sitemap.xml").await;
+
+    // Assert
+    assert_eq!(response.status().as_u16(), 200);
+    let body = response.text().await.unwrap();
+    assert!(body.contains("<loc>http://127.0.0.1/</loc>"));
+    assert!(body.contains("<loc>http://127.0.0.1/blog/published</loc>"));
+    assert!(body.contains("<lastmod>2024-03-01T12:00:00+00:00</lastmod>"));
+    assert!(!body.contains("/blog/draft"));
+}
+
+#[tokio::test]
+async fn default_robots_txt_links_to_the_sitemap() {
+    // Arrange
+    let app = spawn_app().await;
+
+    // Act
+    let response = app.get("robots.txt").await;
+
+    // Assert
+    assert_eq!(response.status().as_u16(), 200);
+    let body = response.text().await.unwrap();
+    assert!(body.contains("Disallow: /admin/"));
+    assert!(body.contains("Sitemap: http://127.0.0.1/sitemap.xml"));
+}
@@ -0,0 +1,138 @@
+use crate::helpers::spawn_app;
+
+#[tokio::test]
+async fn new_post_stores_normalized_tags() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+
+    // Act
+    app.post(
+        "admin/new_post",
+        "url_id=new-post&title=first&markdown=__rad__&publish=true&tags=Rust%2C+web+dev",
+    )
+    .await;
+
+    // Assert
+    let post = app
+        .reader_use_case
+        .get_post_by_url_id("new-post")
+        .await
+        .unwrap()
+        .expect("post should exist");
+    assert_eq!(post.tags, vec!["rust", "web-dev"]);
+
+    let page = app.get("blog/new-post").await.text().await.unwrap();
+    assert!(page.contains(r#"href="/tags/web-dev""#));
+}
+
+#[tokio::test]
+async fn editing_a_post_replaces_its_tags() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=new-post&title=first&markdown=__rad__&publish=true&tags=rust%2Cweb",
+    )
+    .await;
+
+    // Act
+    app.post(
+        "admin/edit/new-post",
+        "url_id=new-post&title=first&markdown=__rad__&publish=true&tags=web%2Csqlite",
+    )
+    .await;
+
+    // Assert
+    let post = app
+        .reader_use_case
+        .get_post_by_url_id("new-post")
+        .await
+        .unwrap()
+        .expect("post should exist");
+    assert_eq!(post.tags, vec!["sqlite", "web"]);
+
+    let tags = app.reader_use_case.get_tags().await.unwrap();
+    let names = tags.iter().map(|t| t.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, vec!["sqlite", "web"]);
+}
+
+#[tokio::test]
+async fn tag_page_lists_only_published_posts_with_the_tag() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=tagged&title=Tagged+post&markdown=__rad__&publish=true&tags=rust",
+    )
+    .await;
+    app.post(
+        "admin/new_post",
+        "url_id=draft&title=Draft+post&markdown=__rad__&tags=rust",
+    )
+    .await;
+    app.post(
+        "admin/new_post",
+        "url_id=other&title=Other+post&markdown=__rad__&publish=true&tags=go",
+    )
+    .await;
+
+    // Act
+    let tag_page = app.get("tags/rust").await.text().await.unwrap();
+    let tags_page = app.get("tags").await.text().await.unwrap();
+
+    // Assert
+    assert!(tag_page.contains("Tagged post"));
+    assert!(!tag_page.contains("Draft post"));
+    assert!(!tag_page.contains("Other post"));
+
+    assert!(tags_page.contains("rust (1)"));
+    assert!(tags_page.contains("go (1)"));
+}
+
+#[tokio::test]
+async fn tag_page_returns_404_for_unknown_tag() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=draft&title=Draft+post&markdown=__rad__&tags=rust",
+    )
+    .await;
+
+    // Act
+    let unknown = app.get("tags/unknown").await;
+    let draft_only = app.get("tags/rust").await;
+
+    // Assert
+    assert_eq!(unknown.status().as_u16(), 404);
+    assert_eq!(draft_only.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn tag_links_encode_slashes_and_resolve_to_the_tag_page() {
+    // Arrange
+    let app = spawn_app().await;
+    app.login().await;
+    app.post(
+        "admin/new_post",
+        "url_id=tagged&title=Tagged&markdown=__rad__&publish=true&tags=c%2Fc%2B%2B",
+    )
+    .await;
+
+    // Act
+    let post_page = app.get("blog/tagged").await.text().await.unwrap();
+    let tags_page = app.get("tags").await.text().await.unwrap();
+    let tag_page = app.get("tags/c%2Fc%2B%2B").await;
+
+    // Assert
+    assert!(post_page.contains(r#"href="/tags/c%2Fc%2B%2B""#));
+    assert!(tags_page.contains(r#"href="/tags/c%2Fc%2B%2B""#));
+    assert_eq!(tag_page.status().as_u16(), 200);
+    let tag_page = tag_page.text().await.unwrap();
+    assert!(tag_page.contains("Posts tagged c/c++"));
+    assert!(tag_page.contains(r#"href="/tags/c%2Fc%2B%2B/feed.atom""#));
+}
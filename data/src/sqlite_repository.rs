@@ -1,9 +1,14 @@
 use anyhow::{anyhow, Context};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use domain::{BlogPost, BlogPostStatus, NewBlogPostData, Repository};
+use domain::{
+    ArchiveMonth, BlogPost, BlogPostStatus, NewBlogPostData, PostOrder, PostQuery, PostRevision,
+    PostSeries, PostStatusFilter, PostSummary, RenderedPost, Repository, SearchResult, Series,
+    SnippetFragment, Tag,
+};
 use secrecy::Secret;
-use sqlx::SqlitePool;
+use sqlx::{SqliteConnection, SqlitePool};
+use std::collections::HashMap;
 use std::error::Error;
 use std::fmt::Display;
 
@@ -16,6 +21,277 @@ impl SqliteRepository {
     pub fn new(pool: SqlitePool) -> Self {
         Self { pool }
     }
+
+    // Converts the records and attaches their tags.
+    async fn to_post_summaries(
+        &self,
+        records: Vec<PostSummaryRecord>,
+    ) -> anyhow::Result<Vec<PostSummary>> {
+        let mut tags = self.load_tags(records.iter().map(|r| r.id)).await?;
+        records
+            .into_iter()
+            .map(|record| {
+                let post_tags = tags.remove(&record.id).unwrap_or_default();
+                record.try_into().map(|post: PostSummary| PostSummary {
+                    tags: post_tags,
+                    ..post
+                })
+            })
+            .collect()
+    }
+
+    // Converts the records and attaches their tags and series.
+    async fn to_blog_posts(&self, records: Vec<BlogPostRecord>) -> anyhow::Result<Vec<BlogPost>> {
+        let mut tags = self.load_tags(records.iter().map(|r| r.id)).await?;
+        let series = self
+            .load_series_names(records.iter().filter_map(|r| r.series_id))
+            .await?;
+
+        records
+            .into_iter()
+            .map(|r| {
+                let post_tags = tags.remove(&r.id).unwrap_or_default();
+                let post_series = match (r.series_id, r.series_part) {
+                    (Some(id), Some(part)) => series.get(&id).map(|(url_id, title)| PostSeries {
+                        url_id: url_id.clone(),
+                        title: title.clone(),
+                        part,
+                    }),
+                    _ => None,
+                };
+                r.try_into().map(|post: BlogPost| BlogPost {
+                    tags: post_tags,
+                    series: post_series,
+                    ..post
+                })
+            })
+            .collect()
+    }
+
+    // Url id and title by series id, for the given series.
+    async fn load_series_names(
+        &self,
+        series_ids: impl Iterator<Item = i64>,
+    ) -> anyhow::Result<HashMap<i64, (String, String)>> {
+        let series_ids = to_json_array(series_ids);
+        let records = sqlx::query!(
+            r#"
+            SELECT id, url_id, title FROM series WHERE id IN (SELECT value FROM json_each($1))
+            "#,
+            series_ids
+        )
+        .fetch_all(&self.pool)
+        .await
+        .error("Failed to read series from the database")?;
+
+        Ok(records
+            .into_iter()
+            .map(|r| (r.id, (r.url_id, r.title)))
+            .collect())
+    }
+
+    // Tag names by post id, for the given posts.
+    async fn load_tags(
+        &self,
+        post_ids: impl Iterator<Item = i64>,
+    ) -> anyhow::Result<HashMap<i64, Vec<String>>> {
+        let post_ids = to_json_array(post_ids);
+        let records = sqlx::query!(
+            r#"
+            SELECT post_tags.post_id, tags.name FROM post_tags
+            JOIN tags ON tags.id = post_tags.tag_id
+            WHERE post_tags.post_id IN (SELECT value FROM json_each($1))
+            ORDER BY tags.name
+            "#,
+            post_ids
+        )
+        .fetch_all(&self.pool)
+        .await
+        .error("Failed to read tags from the database")?;
+
+        let mut tags = HashMap::<i64, Vec<String>>::new();
+        for record in records {
+            tags.entry(record.post_id).or_default().push(record.name);
+        }
+        Ok(tags)
+    }
+}
+
+// Ids as a JSON array, to be used with `json_each` as SQLite has no array parameters.
+fn to_json_array(ids: impl Iterator<Item = i64>) -> String {
+    format!(
+        "[{}]",
+        ids.map(|id| id.to_string()).collect::<Vec<_>>().join(",")
+    )
+}
+
+fn to_toc_json(rendered: &RenderedPost) -> anyhow::Result<Option<String>> {
+    let toc = rendered
+        .toc
+        .as_ref()
+        .map(serde_json::to_string)
+        .transpose()?;
+    Ok(toc)
+}
+
+// Quotes every word so that user input can't contain FTS5 query syntax.
+// The last word is a prefix query to also match partially typed words.
+fn to_fts_query(query: &str) -> String {
+    let words = query
+        .split_whitespace()
+        .map(|word| format!("\"{}\"", word.replace('"', "\"\"")))
+        .collect::<Vec<_>>();
+
+    match words.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} {last}*", rest.join(" ")),
+        Some((last, _)) => format!("{last}*"),
+        None => String::new(),
+    }
+}
+
+// Splits a snippet on the char(2) and char(3) markers around matches.
+fn to_snippet_fragments(snippet: &str) -> Vec<SnippetFragment> {
+    let mut fragments = Vec::new();
+    for (i, part) in snippet.split('\u{2}').enumerate() {
+        let (highlighted, rest) = match part.split_once('\u{3}') {
+            Some((matched, rest)) if i > 0 => (Some(matched), rest),
+            _ => (None, part),
+        };
+        if let Some(text) = highlighted {
+            fragments.push(SnippetFragment {
+                text: text.to_string(),
+                highlighted: true,
+            });
+        }
+        if !rest.is_empty() {
+            fragments.push(SnippetFragment {
+                text: rest.to_string(),
+                highlighted: false,
+            });
+        }
+    }
+    fragments
+}
+
+async fn set_post_series(
+    connection: &mut SqliteConnection,
+    post_id: i64,
+    post: &NewBlogPostData,
+) -> anyhow::Result<()> {
+    let series_id = match (post.series_url_id(), &post.series) {
+        (Some(url_id), Some(title)) => {
+            // A new title with the same url id renames the series
+            sqlx::query!(
+                r#"
+                INSERT INTO series (url_id, title) VALUES ($1, $2)
+                ON CONFLICT (url_id) DO UPDATE SET title = excluded.title
+                "#,
+                url_id,
+                title
+            )
+            .execute(&mut *connection)
+            .await
+            .error("Failed to insert series into the database")?;
+
+            let id = sqlx::query!(
+                r#"
+                SELECT id FROM series WHERE url_id = $1
+                "#,
+                url_id
+            )
+            .fetch_one(&mut *connection)
+            .await
+            .error("Failed to read series from the database")?
+            .id;
+            Some(id)
+        }
+        _ => None,
+    };
+
+    // Without an explicit part number a post keeps its part if it stays in the
+    // same series, and otherwise becomes the last part.
+    sqlx::query!(
+        r#"
+        UPDATE posts
+        SET series_part = CASE
+                WHEN $1 IS NULL THEN NULL
+                ELSE COALESCE(
+                    $2,
+                    CASE WHEN series_id = $1 THEN series_part END,
+                    (SELECT COALESCE(MAX(p.series_part), 0) + 1 FROM posts p
+                     WHERE p.series_id = $1 AND p.id != $3)
+                )
+            END,
+            series_id = $1
+        WHERE id = $3
+        "#,
+        series_id,
+        post.series_part,
+        post_id
+    )
+    .execute(&mut *connection)
+    .await
+    .error("Failed to update the series of a blog post")?;
+
+    // Remove series that no longer have any posts
+    sqlx::query!(
+        r#"
+        DELETE FROM series WHERE id NOT IN (SELECT series_id FROM posts WHERE series_id IS NOT NULL)
+        "#
+    )
+    .execute(&mut *connection)
+    .await
+    .unit_error("Failed to delete unused series")
+}
+
+async fn replace_post_tags(
+    connection: &mut SqliteConnection,
+    post_id: i64,
+    tags: &[String],
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"
+        DELETE FROM post_tags WHERE post_id = $1
+        "#,
+        post_id
+    )
+    .execute(&mut *connection)
+    .await
+    .error("Failed to delete blog post tags")?;
+
+    for tag in tags {
+        sqlx::query!(
+            r#"
+            INSERT OR IGNORE INTO tags (name) VALUES ($1)
+            "#,
+            tag
+        )
+        .execute(&mut *connection)
+        .await
+        .error("Failed to insert tag into the database")?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO post_tags (post_id, tag_id)
+            SELECT $1, id FROM tags WHERE name = $2
+            "#,
+            post_id,
+            tag
+        )
+        .execute(&mut *connection)
+        .await
+        .error("Failed to insert blog post tag into the database")?;
+    }
+
+    // Remove tags that are no longer used by any post
+    sqlx::query!(
+        r#"
+        DELETE FROM tags WHERE id NOT IN (SELECT tag_id FROM post_tags)
+        "#
+    )
+    .execute(&mut *connection)
+    .await
+    .unit_error("Failed to delete unused tags")
 }
 
 #[async_trait]
@@ -59,32 +335,207 @@ impl Repository for SqliteRepository {
         Ok(pw)
     }
 
-    async fn store_blog_post(&self, new_post: &NewBlogPostData, html: &str) -> anyhow::Result<()> {
+    async fn store_blog_post(
+        &self,
+        new_post: &NewBlogPostData,
+        rendered: &RenderedPost,
+    ) -> anyhow::Result<()> {
         let published_at = if new_post.publish {
-            Some(Utc::now().timestamp())
+            Some(new_post.publish_at.unwrap_or_else(Utc::now).timestamp())
         } else {
             None
         };
 
-        sqlx::query!(
+        let word_count = i64::try_from(rendered.word_count)?;
+        let toc = to_toc_json(rendered)?;
+        let mut transaction = self.pool.begin().await?;
+
+        let id = sqlx::query!(
             r#"
-            INSERT INTO posts (url_id, title, markdown, html, is_published, published_at)
-            VALUES ($1, $2, $3, $4, $5, $6)
+            INSERT INTO posts (
+                url_id, title, markdown, html, is_published, published_at, summary, excerpt_html,
+                word_count, toc
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
             "#,
             new_post.url_id,
             new_post.title,
             new_post.markdown,
-            html,
+            rendered.html,
             new_post.publish,
-            published_at
+            published_at,
+            new_post.summary,
+            rendered.excerpt_html,
+            word_count,
+            toc
         )
-        .execute(&self.pool)
+        .execute(&mut *transaction)
         .await
-        .error("Failed to insert new blog post into the database")?;
+        .error("Failed to insert new blog post into the database")?
+        .last_insert_rowid();
 
+        replace_post_tags(&mut transaction, id, &new_post.tags).await?;
+        set_post_series(&mut transaction, id, new_post).await?;
+
+        transaction.commit().await?;
         Ok(())
     }
 
+    async fn update_blog_post(
+        &self,
+        id: i64,
+        post: &NewBlogPostData,
+        rendered: &RenderedPost,
+    ) -> anyhow::Result<()> {
+        let now = Utc::now().timestamp();
+        let publish_at = post.publish_at.map(|dt| dt.timestamp());
+        let word_count = i64::try_from(rendered.word_count)?;
+        let toc = to_toc_json(rendered)?;
+        let mut transaction = self.pool.begin().await?;
+
+        // Keep the version being replaced, unless the edit didn't change its content.
+        sqlx::query!(
+            r#"
+            INSERT INTO post_revisions (post_id, title, markdown, created_at)
+            SELECT id, title, markdown, $1 FROM posts
+            WHERE id = $2 AND (title != $3 OR markdown != $4)
+            "#,
+            now,
+            id,
+            post.title,
+            post.markdown
+        )
+        .execute(&mut *transaction)
+        .await
+        .error("Failed to store blog post revision in the database")?;
+
+        // An already published post keeps its original publish date unless a new one is given.
+        // A trashed post keeps its published state so that restoring it brings it back as it was.
+        sqlx::query!(
+            r#"
+            UPDATE posts
+            SET url_id = $1,
+                title = $2,
+                markdown = $3,
+                html = $4,
+                is_published = CASE WHEN deleted_at IS NULL THEN $5 ELSE is_published END,
+                published_at = CASE
+                    WHEN deleted_at IS NULL AND $5 THEN COALESCE($6, published_at, $7)
+                    ELSE published_at
+                END,
+                updated_at = $7,
+                summary = $8,
+                excerpt_html = $9,
+                word_count = $10,
+                toc = $11
+            WHERE id = $12
+            "#,
+            post.url_id,
+            post.title,
+            post.markdown,
+            rendered.html,
+            post.publish,
+            publish_at,
+            now,
+            post.summary,
+            rendered.excerpt_html,
+            word_count,
+            toc,
+            id
+        )
+        .execute(&mut *transaction)
+        .await
+        .error("Failed to update blog post in the database")?;
+
+        replace_post_tags(&mut transaction, id, &post.tags).await?;
+        set_post_series(&mut transaction, id, post).await?;
+
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    async fn update_post_html(&self, id: i64, rendered: &RenderedPost) -> anyhow::Result<()> {
+        let word_count = i64::try_from(rendered.word_count)?;
+        let toc = to_toc_json(rendered)?;
+        sqlx::query!(
+            r#"
+            UPDATE posts SET html = $1, excerpt_html = $2, word_count = $3, toc = $4 WHERE id = $5
+            "#,
+            rendered.html,
+            rendered.excerpt_html,
+            word_count,
+            toc,
+            id
+        )
+        .execute(&self.pool)
+        .await
+        .unit_error("Failed to update the HTML of a blog post")
+    }
+
+    async fn update_post_published(&self, id: i64, publish: bool) -> anyhow::Result<()> {
+        let now = Utc::now().timestamp();
+
+        // Republishing a post keeps the date it was first published.
+        sqlx::query!(
+            r#"
+            UPDATE posts
+            SET is_published = $1,
+                published_at = CASE WHEN $1 THEN COALESCE(published_at, $2) ELSE published_at END
+            WHERE id = $3
+            "#,
+            publish,
+            now,
+            id
+        )
+        .execute(&self.pool)
+        .await
+        .unit_error("Failed to update the published state of a blog post")
+    }
+
+    async fn update_post_pinned(&self, id: i64, pinned: bool) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE posts SET is_pinned = $1 WHERE id = $2
+            "#,
+            pinned,
+            id
+        )
+        .execute(&self.pool)
+        .await
+        .unit_error("Failed to update the pinned state of a blog post")
+    }
+
+    async fn update_post_trashed(&self, id: i64, trashed: bool) -> anyhow::Result<()> {
+        let deleted_at = if trashed {
+            Some(Utc::now().timestamp())
+        } else {
+            None
+        };
+
+        sqlx::query!(
+            r#"
+            UPDATE posts SET deleted_at = $1 WHERE id = $2
+            "#,
+            deleted_at,
+            id
+        )
+        .execute(&self.pool)
+        .await
+        .unit_error("Failed to update the trashed state of a blog post")
+    }
+
+    async fn delete_post(&self, id: i64) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM posts WHERE id = $1
+            "#,
+            id
+        )
+        .execute(&self.pool)
+        .await
+        .unit_error("Failed to delete blog post from the database")
+    }
+
     async fn load_all_posts(&self) -> anyhow::Result<Vec<BlogPost>> {
         let records = sqlx::query_as!(
             BlogPostRecord,
@@ -96,10 +547,73 @@ impl Repository for SqliteRepository {
         .await
         .error("Failed to read blog post from the database")?;
 
-        Ok(records
-            .into_iter()
-            .map(|r| r.try_into())
-            .collect::<anyhow::Result<Vec<BlogPost>>>()?)
+        self.to_blog_posts(records).await
+    }
+
+    async fn list_posts(
+        &self,
+        now: DateTime<Utc>,
+        filter: Option<PostStatusFilter>,
+    ) -> anyhow::Result<Vec<BlogPost>> {
+        let now = now.timestamp();
+        let status = filter.map(|filter| match filter {
+            PostStatusFilter::Draft => "draft",
+            PostStatusFilter::Published => "published",
+            PostStatusFilter::Scheduled => "scheduled",
+            PostStatusFilter::Trashed => "trashed",
+        });
+        let records = sqlx::query_as!(
+            BlogPostRecord,
+            r#"
+            SELECT * FROM posts
+            WHERE CASE $1
+                WHEN 'draft' THEN deleted_at IS NULL AND is_published = 0
+                WHEN 'published' THEN deleted_at IS NULL AND is_published = 1
+                    AND published_at <= $2
+                WHEN 'scheduled' THEN deleted_at IS NULL AND is_published = 1
+                    AND published_at > $2
+                WHEN 'trashed' THEN deleted_at IS NOT NULL
+                ELSE deleted_at IS NULL
+            END
+            ORDER BY is_published = 1, CASE WHEN is_published = 1 THEN published_at END DESC,
+                id DESC
+            "#,
+            status,
+            now
+        )
+        .fetch_all(&self.pool)
+        .await
+        .error("Failed to read blog posts from the database")?;
+
+        self.to_blog_posts(records).await
+    }
+
+    async fn load_unrendered_posts(&self) -> anyhow::Result<Vec<BlogPost>> {
+        let records = sqlx::query_as!(
+            BlogPostRecord,
+            r#"
+            SELECT * FROM posts WHERE excerpt_html IS NULL OR word_count IS NULL
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .error("Failed to read blog post from the database")?;
+
+        self.to_blog_posts(records).await
+    }
+
+    async fn load_posts_without_toc(&self) -> anyhow::Result<Vec<BlogPost>> {
+        let records = sqlx::query_as!(
+            BlogPostRecord,
+            r#"
+            SELECT * FROM posts WHERE toc IS NULL AND markdown LIKE '%[TOC]%'
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .error("Failed to read blog post from the database")?;
+
+        self.to_blog_posts(records).await
     }
 
     async fn load_post_by_url_id(&self, url_id: &str) -> anyhow::Result<Option<BlogPost>> {
@@ -116,11 +630,267 @@ impl Repository for SqliteRepository {
 
         let post = match record {
             None => None,
-            Some(record) => Some(record.try_into()?),
+            Some(record) => self.to_blog_posts(vec![record]).await?.pop(),
         };
 
         Ok(post)
     }
+
+    async fn list_published_posts(
+        &self,
+        now: DateTime<Utc>,
+        query: &PostQuery,
+    ) -> anyhow::Result<Vec<PostSummary>> {
+        let now = now.timestamp();
+        let from = query.published_from.map(|dt| dt.timestamp());
+        let until = query.published_until.map(|dt| dt.timestamp());
+        let newest_first = query.order == PostOrder::NewestFirst;
+        let records = sqlx::query_as!(
+            PostSummaryRecord,
+            r#"
+            SELECT id, url_id, title, is_published, published_at, updated_at, deleted_at, is_pinned,
+                excerpt_html, word_count
+            FROM posts
+            WHERE is_published = 1 AND deleted_at IS NULL AND published_at <= $1
+                AND ($2 IS NULL OR published_at >= $2)
+                AND ($3 IS NULL OR published_at < $3)
+                AND ($4 IS NULL OR id IN (
+                    SELECT post_tags.post_id FROM post_tags
+                    JOIN tags ON tags.id = post_tags.tag_id
+                    WHERE tags.name = $4
+                ))
+            ORDER BY
+                CASE WHEN $5 THEN is_pinned END DESC,
+                CASE WHEN $6 THEN published_at END DESC,
+                CASE WHEN $6 THEN id END DESC,
+                published_at,
+                id
+            LIMIT COALESCE($7, -1) OFFSET $8
+            "#,
+            now,
+            from,
+            until,
+            query.tag,
+            query.pinned_first,
+            newest_first,
+            query.limit,
+            query.offset
+        )
+        .fetch_all(&self.pool)
+        .await
+        .error("Failed to list published blog posts from the database")?;
+
+        self.to_post_summaries(records).await
+    }
+
+    async fn load_posts_by_ids(&self, ids: &[i64]) -> anyhow::Result<Vec<BlogPost>> {
+        let ids = to_json_array(ids.iter().copied());
+        let records = sqlx::query_as!(
+            BlogPostRecord,
+            r#"
+            SELECT * FROM posts WHERE id IN (SELECT value FROM json_each($1))
+            "#,
+            ids
+        )
+        .fetch_all(&self.pool)
+        .await
+        .error("Failed to read blog posts from the database")?;
+
+        self.to_blog_posts(records).await
+    }
+
+    async fn count_published_posts_by_month(
+        &self,
+        now: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<ArchiveMonth>> {
+        let now = now.timestamp();
+        let records = sqlx::query!(
+            r#"
+            SELECT
+                CAST(strftime('%Y', published_at, 'unixepoch') AS INTEGER) AS "year!: i64",
+                CAST(strftime('%m', published_at, 'unixepoch') AS INTEGER) AS "month!: i64",
+                COUNT(*) AS "post_count!: i64"
+            FROM posts
+            WHERE is_published = 1 AND deleted_at IS NULL AND published_at <= $1
+            GROUP BY 1, 2
+            ORDER BY 1 DESC, 2 DESC
+            "#,
+            now
+        )
+        .fetch_all(&self.pool)
+        .await
+        .error("Failed to count published blog posts in the database")?;
+
+        records
+            .into_iter()
+            .map(|record| {
+                Ok(ArchiveMonth {
+                    year: record.year.try_into()?,
+                    month: record.month.try_into()?,
+                    post_count: record.post_count.try_into()?,
+                })
+            })
+            .collect()
+    }
+
+    async fn count_published_posts_by_tag(&self, now: DateTime<Utc>) -> anyhow::Result<Vec<Tag>> {
+        let now = now.timestamp();
+        let records = sqlx::query!(
+            r#"
+            SELECT tags.name, COUNT(*) AS "post_count!: i64"
+            FROM tags
+            JOIN post_tags ON post_tags.tag_id = tags.id
+            JOIN posts ON posts.id = post_tags.post_id
+            WHERE posts.is_published = 1 AND posts.deleted_at IS NULL AND posts.published_at <= $1
+            GROUP BY tags.id
+            ORDER BY tags.name
+            "#,
+            now
+        )
+        .fetch_all(&self.pool)
+        .await
+        .error("Failed to count tagged blog posts in the database")?;
+
+        records
+            .into_iter()
+            .map(|record| {
+                Ok(Tag {
+                    name: record.name,
+                    post_count: record.post_count.try_into()?,
+                })
+            })
+            .collect()
+    }
+
+    async fn search_posts(
+        &self,
+        now: DateTime<Utc>,
+        query: &str,
+        limit: u32,
+    ) -> anyhow::Result<Vec<SearchResult>> {
+        let fts_query = to_fts_query(query);
+        if fts_query.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Matches in the title weigh more than matches in the body
+        let now = now.timestamp();
+        let records = sqlx::query!(
+            r#"
+            SELECT posts.id, posts.url_id, posts.title, posts.is_published, posts.published_at,
+                   posts.updated_at, posts.deleted_at, posts.is_pinned, posts.excerpt_html,
+                   posts.word_count,
+                   snippet(posts_fts, -1, char(2), char(3), '…', 24) AS "snippet!: String"
+            FROM posts_fts
+            JOIN posts ON posts.id = posts_fts.rowid
+            WHERE posts_fts MATCH $1
+                AND posts.is_published = 1 AND posts.deleted_at IS NULL AND posts.published_at <= $2
+            ORDER BY bm25(posts_fts, 10.0, 1.0)
+            LIMIT $3
+            "#,
+            fts_query,
+            now,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await
+        .error("Failed to search blog posts in the database")?;
+
+        let snippets = records
+            .iter()
+            .map(|r| to_snippet_fragments(&r.snippet))
+            .collect::<Vec<_>>();
+        let summaries = records
+            .into_iter()
+            .map(|r| PostSummaryRecord {
+                id: r.id,
+                url_id: r.url_id,
+                title: r.title,
+                is_published: r.is_published,
+                published_at: r.published_at,
+                updated_at: r.updated_at,
+                deleted_at: r.deleted_at,
+                is_pinned: r.is_pinned,
+                excerpt_html: r.excerpt_html,
+                word_count: r.word_count,
+            })
+            .collect();
+
+        Ok(self
+            .to_post_summaries(summaries)
+            .await?
+            .into_iter()
+            .zip(snippets)
+            .map(|(post, snippet)| SearchResult { post, snippet })
+            .collect())
+    }
+
+    async fn load_series(&self, url_id: &str) -> anyhow::Result<Option<Series>> {
+        let Some(series) = sqlx::query!(
+            r#"
+            SELECT id, title FROM series WHERE url_id = $1
+            "#,
+            url_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .error("Failed to read series from the database")?
+        else {
+            return Ok(None);
+        };
+
+        let records = sqlx::query_as!(
+            BlogPostRecord,
+            r#"
+            SELECT * FROM posts WHERE series_id = $1 ORDER BY series_part, id
+            "#,
+            series.id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .error("Failed to read series blog posts from the database")?;
+
+        Ok(Some(Series {
+            url_id: url_id.to_string(),
+            title: series.title,
+            posts: self.to_blog_posts(records).await?,
+        }))
+    }
+
+    async fn load_post_revisions(&self, post_id: i64) -> anyhow::Result<Vec<PostRevision>> {
+        let records = sqlx::query_as!(
+            PostRevisionRecord,
+            r#"
+            SELECT * FROM post_revisions WHERE post_id = $1 ORDER BY id DESC
+            "#,
+            post_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .error("Failed to read blog post revisions from the database")?;
+
+        records.into_iter().map(|r| r.try_into()).collect()
+    }
+
+    async fn load_post_revision(
+        &self,
+        post_id: i64,
+        revision_id: i64,
+    ) -> anyhow::Result<Option<PostRevision>> {
+        let record = sqlx::query_as!(
+            PostRevisionRecord,
+            r#"
+            SELECT * FROM post_revisions WHERE post_id = $1 AND id = $2
+            "#,
+            post_id,
+            revision_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .error("Failed to read blog post revision from the database")?;
+
+        record.map(|r| r.try_into()).transpose()
+    }
 }
 
 struct BlogPostRecord {
@@ -132,40 +902,127 @@ struct BlogPostRecord {
     pub is_published: i64,
     pub published_at: Option<i64>,
     pub updated_at: Option<i64>,
+    pub deleted_at: Option<i64>,
+    pub series_id: Option<i64>,
+    pub series_part: Option<i64>,
+    pub is_pinned: i64,
+    pub summary: Option<String>,
+    pub excerpt_html: Option<String>,
+    pub word_count: Option<i64>,
+    pub toc: Option<String>,
 }
 
 impl TryInto<BlogPost> for BlogPostRecord {
     type Error = anyhow::Error;
 
     fn try_into(self) -> Result<BlogPost, Self::Error> {
-        let status = if self.is_published == 1 {
-            BlogPostStatus::Published(
-                self.published_at
-                    .ok_or_else(|| anyhow!("is_published is true but published_at is null"))?
-                    .to_datetime_utc()
-                    .context("published_at contains invalid data")?,
-            )
-        } else {
-            BlogPostStatus::Unpublished
-        };
-
-        let updated_at = if let Some(ts) = self.updated_at {
-            Some(
-                ts.to_datetime_utc()
-                    .context("updated_at contains invalid data")?,
-            )
-        } else {
-            None
-        };
-
         Ok(BlogPost {
             id: self.id,
             url_id: self.url_id,
             title: self.title,
             html: self.html,
             markdown: self.markdown,
-            status,
-            updated_at,
+            status: to_post_status(self.is_published, self.published_at, self.deleted_at)?,
+            updated_at: to_updated_at(self.updated_at)?,
+            tags: Vec::new(),
+            series: None,
+            pinned: self.is_pinned == 1,
+            summary: self.summary,
+            excerpt_html: self.excerpt_html.unwrap_or_default(),
+            word_count: self.word_count.unwrap_or_default().try_into()?,
+            toc: self
+                .toc
+                .map(|toc| serde_json::from_str(&toc))
+                .transpose()
+                .context("toc contains invalid data")?,
+        })
+    }
+}
+
+struct PostSummaryRecord {
+    pub id: i64,
+    pub url_id: String,
+    pub title: String,
+    pub is_published: i64,
+    pub published_at: Option<i64>,
+    pub updated_at: Option<i64>,
+    pub deleted_at: Option<i64>,
+    pub is_pinned: i64,
+    pub excerpt_html: Option<String>,
+    pub word_count: Option<i64>,
+}
+
+impl TryInto<PostSummary> for PostSummaryRecord {
+    type Error = anyhow::Error;
+
+    fn try_into(self) -> Result<PostSummary, Self::Error> {
+        Ok(PostSummary {
+            id: self.id,
+            url_id: self.url_id,
+            title: self.title,
+            status: to_post_status(self.is_published, self.published_at, self.deleted_at)?,
+            updated_at: to_updated_at(self.updated_at)?,
+            tags: Vec::new(),
+            pinned: self.is_pinned == 1,
+            excerpt_html: self.excerpt_html.unwrap_or_default(),
+            word_count: self.word_count.unwrap_or_default().try_into()?,
+        })
+    }
+}
+
+fn to_post_status(
+    is_published: i64,
+    published_at: Option<i64>,
+    deleted_at: Option<i64>,
+) -> anyhow::Result<BlogPostStatus> {
+    let status = if let Some(ts) = deleted_at {
+        BlogPostStatus::Trashed(
+            ts.to_datetime_utc()
+                .context("deleted_at contains invalid data")?,
+        )
+    } else if is_published == 1 {
+        BlogPostStatus::published(
+            published_at
+                .ok_or_else(|| anyhow!("is_published is true but published_at is null"))?
+                .to_datetime_utc()
+                .context("published_at contains invalid data")?,
+        )
+    } else {
+        BlogPostStatus::Unpublished
+    };
+    Ok(status)
+}
+
+fn to_updated_at(updated_at: Option<i64>) -> anyhow::Result<Option<DateTime<Utc>>> {
+    updated_at
+        .map(|ts| {
+            ts.to_datetime_utc()
+                .context("updated_at contains invalid data")
+        })
+        .transpose()
+}
+
+struct PostRevisionRecord {
+    pub id: i64,
+    pub post_id: i64,
+    pub title: String,
+    pub markdown: String,
+    pub created_at: i64,
+}
+
+impl TryInto<PostRevision> for PostRevisionRecord {
+    type Error = anyhow::Error;
+
+    fn try_into(self) -> Result<PostRevision, Self::Error> {
+        Ok(PostRevision {
+            id: self.id,
+            post_id: self.post_id,
+            title: self.title,
+            markdown: self.markdown,
+            created_at: self
+                .created_at
+                .to_datetime_utc()
+                .context("created_at contains invalid data")?,
         })
     }
 }
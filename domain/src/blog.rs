@@ -1,4 +1,11 @@
-use chrono::{DateTime, Utc};
+use crate::{front_matter, markdown, DomainError, MarkdownSettings, TocEntry};
+use chrono::{DateTime, Month, NaiveDateTime, Utc};
+use serde::{Deserialize, Deserializer};
+use std::fmt::Display;
+use std::str::FromStr;
+
+const DESCRIPTION_MAX_LENGTH: usize = 200;
+const WORDS_PER_MINUTE: usize = 200;
 
 #[derive(Debug)]
 pub struct BlogPost {
@@ -9,6 +16,17 @@ pub struct BlogPost {
     pub markdown: String,
     pub status: BlogPostStatus,
     pub updated_at: Option<DateTime<Utc>>,
+    pub tags: Vec<String>,
+    pub series: Option<PostSeries>,
+    pub pinned: bool,
+    /// Written by the author, replaces the excerpt taken from the post.
+    pub summary: Option<String>,
+    pub excerpt_html: String,
+    /// Words in the text of the post, not counting code blocks.
+    pub word_count: usize,
+    /// The headings of the post, if it asks for a table of contents with a
+    /// `[TOC]` marker.
+    pub toc: Option<Vec<TocEntry>>,
 }
 
 impl BlogPost {
@@ -16,26 +34,456 @@ impl BlogPost {
         matches!(self.status, BlogPostStatus::Published(_))
     }
 
+    pub fn is_scheduled(&self) -> bool {
+        matches!(self.status, BlogPostStatus::Scheduled(_))
+    }
+
+    pub fn is_trashed(&self) -> bool {
+        matches!(self.status, BlogPostStatus::Trashed(_))
+    }
+
+    /// A short plain text description of the post, e.g. for meta tags.
+    pub fn description(&self, settings: &MarkdownSettings) -> String {
+        markdown::plain_text_excerpt(
+            self.excerpt_markdown(settings),
+            settings,
+            DESCRIPTION_MAX_LENGTH,
+        )
+    }
+
+    /// The summary, or the markdown up to `<!-- more -->` or the first
+    /// paragraph.
+    pub fn excerpt_markdown(&self, settings: &MarkdownSettings) -> &str {
+        excerpt_markdown(&self.markdown, self.summary.as_deref(), settings)
+    }
+
+    /// The first image in the post.
+    pub fn cover_image(&self, settings: &MarkdownSettings) -> Option<String> {
+        markdown::first_image_url(&self.markdown, settings)
+    }
+
     pub fn published_at(&self) -> Option<DateTime<Utc>> {
-        if let BlogPostStatus::Published(dt) = self.status {
-            Some(dt)
-        } else {
-            None
-        }
+        self.status.published_at()
+    }
+
+    pub fn reading_time_minutes(&self) -> usize {
+        reading_time_minutes(self.word_count)
+    }
+}
+
+/// A post without its contents, for listing posts.
+#[derive(Debug)]
+pub struct PostSummary {
+    pub id: i64,
+    pub url_id: String,
+    pub title: String,
+    pub status: BlogPostStatus,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub tags: Vec<String>,
+    pub pinned: bool,
+    pub excerpt_html: String,
+    pub word_count: usize,
+}
+
+impl PostSummary {
+    pub fn published_at(&self) -> Option<DateTime<Utc>> {
+        self.status.published_at()
+    }
+
+    pub fn reading_time_minutes(&self) -> usize {
+        reading_time_minutes(self.word_count)
+    }
+}
+
+/// Estimated minutes to read a text, at least one.
+fn reading_time_minutes(word_count: usize) -> usize {
+    word_count.div_ceil(WORDS_PER_MINUTE).max(1)
+}
+
+/// The order to list posts in, by publish date.
+#[derive(serde::Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PostOrder {
+    #[default]
+    NewestFirst,
+    OldestFirst,
+}
+
+/// Which published posts to list. The default lists all of them, newest
+/// first.
+#[derive(Debug, Default, Clone)]
+pub struct PostQuery {
+    pub tag: Option<String>,
+    /// Only posts published at or after this time.
+    pub published_from: Option<DateTime<Utc>>,
+    /// Only posts published before this time.
+    pub published_until: Option<DateTime<Utc>>,
+    pub order: PostOrder,
+    /// List pinned posts before all others.
+    pub pinned_first: bool,
+    pub offset: u32,
+    pub limit: Option<u32>,
+}
+
+/// The series a post is a part of.
+#[derive(Debug, Clone)]
+pub struct PostSeries {
+    pub url_id: String,
+    pub title: String,
+    pub part: i64,
+}
+
+/// A series with its posts ordered by part.
+#[derive(Debug)]
+pub struct Series {
+    pub url_id: String,
+    pub title: String,
+    pub posts: Vec<BlogPost>,
+}
+
+impl Series {
+    /// Index of the post in `posts`, if it is a part of this series.
+    pub fn position(&self, post_id: i64) -> Option<usize> {
+        self.posts.iter().position(|post| post.id == post_id)
+    }
+}
+
+/// The HTML rendered from the markdown of a post.
+#[derive(Debug)]
+pub struct RenderedPost {
+    pub html: String,
+    pub excerpt_html: String,
+    pub word_count: usize,
+    pub toc: Option<Vec<TocEntry>>,
+}
+
+fn excerpt_markdown<'a>(
+    markdown: &'a str,
+    summary: Option<&'a str>,
+    settings: &MarkdownSettings,
+) -> &'a str {
+    summary
+        .filter(|summary| !summary.trim().is_empty())
+        .unwrap_or_else(|| markdown::excerpt(markdown, settings))
+}
+
+/// One page of published posts.
+#[derive(Debug)]
+pub struct PostsPage {
+    pub posts: Vec<PostSummary>,
+    /// Starts at 1.
+    pub page: u32,
+    pub has_next: bool,
+}
+
+impl PostsPage {
+    pub fn has_previous(&self) -> bool {
+        self.page > 1
     }
 }
 
+/// Number of published posts in one month of the archive.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchiveMonth {
+    pub year: i32,
+    /// 1 to 12.
+    pub month: u32,
+    pub post_count: usize,
+}
+
+impl ArchiveMonth {
+    pub fn name(&self) -> &'static str {
+        u8::try_from(self.month)
+            .ok()
+            .and_then(|month| Month::try_from(month).ok())
+            .map(|month| month.name())
+            .unwrap_or_default()
+    }
+}
+
+/// Number of published posts in one year of the archive, with the months
+/// that have posts, newest first.
+#[derive(Debug, PartialEq)]
+pub struct ArchiveYear {
+    pub year: i32,
+    pub post_count: usize,
+    pub months: Vec<ArchiveMonth>,
+}
+
+/// A post matching a search, with an excerpt of the matching text.
+#[derive(Debug)]
+pub struct SearchResult {
+    pub post: PostSummary,
+    pub snippet: Vec<SnippetFragment>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SnippetFragment {
+    pub text: String,
+    /// True if the text matched the search terms.
+    pub highlighted: bool,
+}
+
 #[derive(Debug)]
 pub enum BlogPostStatus {
     Published(DateTime<Utc>),
+    /// Marked as published but with a publish date in the future. Readers
+    /// can't see the post until the date has passed.
+    Scheduled(DateTime<Utc>),
     Unpublished,
+    /// Soft-deleted at the given time. Restoring the post brings back the
+    /// status it had before it was trashed.
+    Trashed(DateTime<Utc>),
 }
 
-#[derive(serde::Deserialize, Debug)]
+impl BlogPostStatus {
+    /// The status of a post that is published at `published_at`.
+    pub fn published(published_at: DateTime<Utc>) -> Self {
+        if published_at > Utc::now() {
+            BlogPostStatus::Scheduled(published_at)
+        } else {
+            BlogPostStatus::Published(published_at)
+        }
+    }
+
+    pub fn published_at(&self) -> Option<DateTime<Utc>> {
+        match self {
+            BlogPostStatus::Published(dt) | BlogPostStatus::Scheduled(dt) => Some(*dt),
+            _ => None,
+        }
+    }
+}
+
+#[derive(serde::Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PostStatusFilter {
+    Draft,
+    Published,
+    Scheduled,
+    Trashed,
+}
+
+#[derive(serde::Deserialize, Debug, Clone)]
 pub struct NewBlogPostData {
     pub title: String,
     pub url_id: String,
     pub markdown: String,
     #[serde(default)]
     pub publish: bool,
+    /// Publish date to use instead of the current time. A date in the future
+    /// schedules the post.
+    #[serde(default, deserialize_with = "deserialize_publish_at")]
+    pub publish_at: Option<DateTime<Utc>>,
+    /// Comma separated in the form data.
+    #[serde(default, deserialize_with = "deserialize_tags")]
+    pub tags: Vec<String>,
+    /// Title of the series this post is a part of.
+    #[serde(default, deserialize_with = "deserialize_empty_as_none")]
+    pub series: Option<String>,
+    /// Part number within the series. When missing, the post keeps its
+    /// current part number or is added as the last part.
+    #[serde(default, deserialize_with = "deserialize_empty_as_none")]
+    pub series_part: Option<i64>,
+    /// Replaces the excerpt taken from the markdown.
+    #[serde(default, deserialize_with = "deserialize_empty_as_none")]
+    pub summary: Option<String>,
+}
+
+impl NewBlogPostData {
+    /// Takes the fields given in the front matter of the markdown, if it has
+    /// any, over the ones from the form and removes the front matter from the
+    /// markdown. A draft flag in the front matter decides if the post is
+    /// published.
+    pub(crate) fn apply_front_matter(&mut self) -> Result<(), DomainError> {
+        let Some((front_matter, body)) =
+            front_matter::parse(&self.markdown).map_err(DomainError::UserValidationError)?
+        else {
+            return Ok(());
+        };
+
+        self.markdown = body.to_string();
+        if let Some(title) = front_matter.title {
+            self.title = title;
+        }
+        if let Some(url_id) = front_matter.url_id {
+            self.url_id = url_id;
+        } else if self.url_id.trim().is_empty() {
+            self.url_id = slugify(&self.title);
+        }
+        if let Some(tags) = front_matter.tags {
+            self.tags = tags;
+        }
+        if front_matter.publish_at.is_some() {
+            self.publish_at = front_matter.publish_at;
+        }
+        if front_matter.summary.is_some() {
+            self.summary = front_matter.summary;
+        }
+        if let Some(draft) = front_matter.draft {
+            self.publish = !draft;
+        }
+        Ok(())
+    }
+
+    /// The summary, or the markdown up to `<!-- more -->` or the first
+    /// paragraph.
+    pub fn excerpt_markdown(&self, settings: &MarkdownSettings) -> &str {
+        excerpt_markdown(&self.markdown, self.summary.as_deref(), settings)
+    }
+
+    pub fn series_url_id(&self) -> Option<String> {
+        self.series
+            .as_deref()
+            .map(slugify)
+            .filter(|s| !s.is_empty())
+    }
+
+    /// Fails if the post has a series title that no url id can be made from.
+    pub(crate) fn validate_series(&self) -> Result<(), DomainError> {
+        if self.series.is_some() && self.series_url_id().is_none() {
+            return Err(DomainError::UserValidationError(
+                "the series title must contain ASCII letters or digits.".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct Tag {
+    pub name: String,
+    pub post_count: usize,
+}
+
+fn deserialize_publish_at<'de, D>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<String>::deserialize(deserializer)?
+        .as_deref()
+        .map(str::trim)
+    {
+        None | Some("") => Ok(None),
+        Some(s) => parse_publish_at(s)
+            .map(Some)
+            .map_err(serde::de::Error::custom),
+    }
+}
+
+fn deserialize_empty_as_none<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    match Option::<String>::deserialize(deserializer)?
+        .as_deref()
+        .map(str::trim)
+    {
+        None | Some("") => Ok(None),
+        Some(s) => s.parse().map(Some).map_err(serde::de::Error::custom),
+    }
+}
+
+fn deserialize_tags<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(parse_tags(&String::deserialize(deserializer)?))
+}
+
+/// Tags are lowercase, with whitespace replaced by dashes.
+pub(crate) fn parse_tags(s: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in s.split(',') {
+        let tag = tag
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join("-")
+            .to_lowercase();
+        if !tag.is_empty() && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+/// Lowercase ASCII letters and digits, everything else becomes single dashes.
+pub(crate) fn slugify(s: &str) -> String {
+    s.to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+// Accepts RFC 3339 as well as the format used by `<input type="datetime-local">`,
+// which has no time zone and is treated as UTC.
+pub(crate) fn parse_publish_at(s: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+    ["%Y-%m-%dT%H:%M", "%Y-%m-%dT%H:%M:%S"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(s, format).ok())
+        .map(|dt| dt.and_utc())
+        .ok_or_else(|| format!("invalid publish date: {s}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+    use claim::{assert_err, assert_ok_eq};
+
+    #[test]
+    fn datetime_local_publish_date_is_parsed_as_utc() {
+        assert_ok_eq!(
+            parse_publish_at("2024-03-01T12:30"),
+            Utc.with_ymd_and_hms(2024, 3, 1, 12, 30, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn rfc3339_publish_date_is_parsed() {
+        assert_ok_eq!(
+            parse_publish_at("2024-03-01T12:30:00+02:00"),
+            Utc.with_ymd_and_hms(2024, 3, 1, 10, 30, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn invalid_publish_date_is_rejected() {
+        assert_err!(parse_publish_at("next tuesday"));
+    }
+
+    #[test]
+    fn tags_are_normalized() {
+        assert_eq!(
+            parse_tags(" Rust, web  dev,,rust ,Async Rust"),
+            vec!["rust", "web-dev", "async-rust"]
+        );
+    }
+
+    #[test]
+    fn empty_tags_are_ignored() {
+        assert!(parse_tags(" , ").is_empty());
+    }
+
+    #[test]
+    fn slugify_keeps_only_ascii_alphanumerics() {
+        assert_eq!(
+            slugify("  Writing a Blog in Rust: Part #1 "),
+            "writing-a-blog-in-rust-part-1"
+        );
+    }
+
+    #[test]
+    fn future_publish_date_is_scheduled() {
+        let status = BlogPostStatus::published(Utc::now() + Duration::days(1));
+        assert!(matches!(status, BlogPostStatus::Scheduled(_)));
+
+        let status = BlogPostStatus::published(Utc::now() - Duration::days(1));
+        assert!(matches!(status, BlogPostStatus::Published(_)));
+    }
 }
@@ -0,0 +1,196 @@
+use crate::util::escape_html;
+use std::ops::RangeInclusive;
+
+/// The info string of a fenced code block, e.g.
+/// ```` ```rust title="main.rs" {3-5} linenos ````.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct CodeBlockInfo {
+    pub language: String,
+    pub title: Option<String>,
+    pub highlighted_lines: Vec<RangeInclusive<usize>>,
+    pub line_numbers: bool,
+}
+
+impl CodeBlockInfo {
+    /// Unknown attributes are ignored, so that info strings meant for other
+    /// renderers still get highlighted.
+    pub(crate) fn parse(info: &str) -> Self {
+        let mut parsed = Self::default();
+        for (i, word) in words(info).into_iter().enumerate() {
+            if let Some(title) = word.strip_prefix("title=") {
+                parsed.title = Some(title.trim_matches('"').to_string());
+            } else if let Some(ranges) = word.strip_prefix('{').and_then(|w| w.strip_suffix('}')) {
+                parsed.highlighted_lines = parse_line_ranges(ranges);
+            } else if word == "linenos" {
+                parsed.line_numbers = true;
+            } else if i == 0 && !word.contains('=') {
+                parsed.language = word.to_string();
+            }
+        }
+        parsed
+    }
+
+    /// Wraps the highlighted code in its `<pre>`, with a line for each line
+    /// of code if it needs line numbers or highlighted lines.
+    pub(crate) fn render(&self, code_html: &str) -> String {
+        let code = if self.line_numbers || !self.highlighted_lines.is_empty() {
+            let lines = split_lines(code_html)
+                .into_iter()
+                .enumerate()
+                .map(|(i, line)| self.render_line(i + 1, &line))
+                .collect::<String>();
+            format!(r#"<pre><code class="lines">{lines}</code></pre>"#)
+        } else {
+            format!("<pre><code>{code_html}</code></pre>")
+        };
+
+        match &self.title {
+            Some(title) => format!(
+                r#"<figure class="code-block"><figcaption class="code-title">{}</figcaption>{code}</figure>"#,
+                escape_html(title)
+            ),
+            None => code,
+        }
+    }
+
+    fn render_line(&self, number: usize, line: &str) -> String {
+        let class = if self.highlighted_lines.iter().any(|r| r.contains(&number)) {
+            "line highlighted"
+        } else {
+            "line"
+        };
+        let number = if self.line_numbers {
+            format!(r#"<span class="line-number" aria-hidden="true">{number}</span>"#)
+        } else {
+            String::new()
+        };
+        format!("<span class=\"{class}\">{number}{line}\n</span>")
+    }
+}
+
+// The words of an info string, where quoted values may contain spaces.
+fn words(info: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut start = None;
+    let mut quoted = false;
+    for (i, c) in info.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if let Some(start) = start.take() {
+                    words.push(&info[start..i]);
+                }
+                continue;
+            }
+            _ => {}
+        }
+        start.get_or_insert(i);
+    }
+    if let Some(start) = start {
+        words.push(&info[start..]);
+    }
+    words
+}
+
+// E.g. "1,3-5". Invalid ranges are skipped.
+fn parse_line_ranges(ranges: &str) -> Vec<RangeInclusive<usize>> {
+    ranges
+        .split(',')
+        .filter_map(|range| {
+            let (start, end) = range.split_once('-').unwrap_or((range, range));
+            let start = start.trim().parse().ok()?;
+            let end = end.trim().parse().ok()?;
+            (start <= end).then_some(start..=end)
+        })
+        .collect()
+}
+
+// Splits highlighted HTML into lines without their newlines, closing the
+// spans that are still open at the end of a line and reopening them on the
+// next, so that every line is valid HTML on its own.
+fn split_lines(html: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut open_spans: Vec<&str> = Vec::new();
+    let mut line = String::new();
+    let mut has_text = false;
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("</span>") {
+            open_spans.pop();
+            line.push_str("</span>");
+            rest = after;
+        } else if rest.starts_with("<span") {
+            let end = rest.find('>').map_or(rest.len(), |i| i + 1);
+            open_spans.push(&rest[..end]);
+            line.push_str(&rest[..end]);
+            rest = &rest[end..];
+        } else if let Some(after) = rest.strip_prefix('\n') {
+            line.push_str(&"</span>".repeat(open_spans.len()));
+            lines.push(std::mem::replace(&mut line, open_spans.concat()));
+            has_text = false;
+            rest = after;
+        } else {
+            let end = rest.find(['<', '\n']).unwrap_or(rest.len());
+            line.push_str(&rest[..end]);
+            has_text = true;
+            rest = &rest[end..];
+        }
+    }
+    // Code ends with a newline, after which only closing tags are left.
+    if has_text {
+        lines.push(line);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_extended_info_string() {
+        assert_eq!(
+            CodeBlockInfo::parse(r#"rust title="src/main file.rs" {1,3-5} linenos"#),
+            CodeBlockInfo {
+                language: "rust".to_string(),
+                title: Some("src/main file.rs".to_string()),
+                highlighted_lines: vec![1..=1, 3..=5],
+                line_numbers: true,
+            }
+        );
+        assert_eq!(
+            CodeBlockInfo::parse("linenos"),
+            CodeBlockInfo {
+                line_numbers: true,
+                ..CodeBlockInfo::default()
+            }
+        );
+        assert_eq!(CodeBlockInfo::parse("python").language, "python");
+    }
+
+    #[test]
+    fn title_is_escaped() {
+        let info = CodeBlockInfo::parse(r#"text title="<Tom's>&co""#);
+
+        assert_eq!(
+            info.render("x"),
+            r#"<figure class="code-block"><figcaption class="code-title">&lt;Tom&#39;s&gt;&amp;co</figcaption><pre><code>x</code></pre></figure>"#
+        );
+    }
+
+    #[test]
+    fn split_lines_reopens_spans() {
+        let html = r#"<span class="a">x<span class="b">/*
+y*/</span>
+</span>"#;
+
+        assert_eq!(
+            split_lines(html),
+            vec![
+                r#"<span class="a">x<span class="b">/*</span></span>"#,
+                r#"<span class="a"><span class="b">y*/</span></span>"#,
+            ]
+        );
+    }
+}
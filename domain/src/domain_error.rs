@@ -6,6 +6,14 @@ pub enum DomainError {
     InvalidRegistrationToken,
     #[error("Incorrect admin password")]
     AdminAuthError,
+    #[error("No blog post with url id {0}")]
+    PostNotFound(String),
+    #[error("No revision with id {0}")]
+    RevisionNotFound(i64),
+    #[error("No page {0}")]
+    PageNotFound(u32),
+    #[error("No published posts with tag {0}")]
+    TagNotFound(String),
     #[error(transparent)]
     UnexpectedError(#[from] anyhow::Error),
 }
@@ -0,0 +1,175 @@
+use crate::blog::{parse_publish_at, parse_tags};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Deserializer};
+
+/// Post metadata at the start of a markdown document, either as YAML
+/// between `---` lines or as TOML between `+++` lines.
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(default)]
+pub(crate) struct FrontMatter {
+    pub title: Option<String>,
+    pub url_id: Option<String>,
+    /// A list or a comma separated string.
+    #[serde(deserialize_with = "deserialize_tags")]
+    pub tags: Option<Vec<String>>,
+    #[serde(rename = "date", deserialize_with = "deserialize_date")]
+    pub publish_at: Option<DateTime<Utc>>,
+    pub summary: Option<String>,
+    pub draft: Option<bool>,
+}
+
+/// The front matter of `markdown`, if it has any, and the markdown after it.
+pub(crate) fn parse(markdown: &str) -> Result<Option<(FrontMatter, &str)>, String> {
+    let Some((delimiter, raw, body)) = split(markdown) else {
+        return Ok(None);
+    };
+
+    let front_matter = match delimiter {
+        "---" => {
+            let yaml = serde_yaml_ng::from_str::<serde_yaml_ng::Value>(raw)
+                .map_err(|e| format!("Invalid YAML front matter: {e}"))?;
+            // Not front matter, e.g. when the document just starts with a thematic break
+            if !yaml.is_mapping() {
+                return Ok(None);
+            }
+            FrontMatter::deserialize(yaml).map_err(|e| format!("Invalid YAML front matter: {e}"))?
+        }
+        _ => toml::from_str(raw).map_err(|e| format!("Invalid TOML front matter: {e}"))?,
+    };
+    Ok(Some((front_matter, body)))
+}
+
+// The delimiter, the front matter between the delimiter lines and the rest.
+fn split(markdown: &str) -> Option<(&'static str, &str, &str)> {
+    let delimiter = ["---", "+++"]
+        .into_iter()
+        .find(|d| markdown.lines().next().map(str::trim_end) == Some(*d))?;
+
+    let start = markdown.find('\n')? + 1;
+    let mut offset = start;
+    for line in markdown[start..].split_inclusive('\n') {
+        if line.trim_end() == delimiter {
+            let raw = &markdown[start..offset];
+            let body = markdown[offset + line.len()..].trim_start_matches(['\r', '\n']);
+            return Some((delimiter, raw, body));
+        }
+        offset += line.len();
+    }
+    None
+}
+
+fn deserialize_tags<'de, D>(deserializer: D) -> Result<Option<Vec<String>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Tags {
+        List(Vec<String>),
+        CommaSeparated(String),
+    }
+
+    Ok(
+        Option::<Tags>::deserialize(deserializer)?.map(|tags| match tags {
+            Tags::List(tags) => parse_tags(&tags.join(",")),
+            Tags::CommaSeparated(tags) => parse_tags(&tags),
+        }),
+    )
+}
+
+fn deserialize_date<'de, D>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    // TOML has a date type of its own, YAML dates are strings
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Date {
+        String(String),
+        Toml(toml::value::Datetime),
+    }
+
+    Option::<Date>::deserialize(deserializer)?
+        .map(|date| match date {
+            Date::String(s) => parse_date(&s),
+            Date::Toml(dt) => parse_date(&dt.to_string()),
+        })
+        .transpose()
+        .map_err(serde::de::Error::custom)
+}
+
+// A date without a time is midnight UTC.
+fn parse_date(s: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc());
+    }
+    parse_publish_at(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_yaml_front_matter() {
+        let markdown = "---\ntitle: \"Hello: world\"\nurl_id: hello\ntags: [Rust, web dev]\ndate: 2024-03-01T12:00:00Z\nsummary: Short.\ndraft: true\n---\n\n# Body\n";
+
+        let (front_matter, body) = parse(markdown).unwrap().unwrap();
+
+        assert_eq!(body, "# Body\n");
+        assert_eq!(front_matter.title.as_deref(), Some("Hello: world"));
+        assert_eq!(front_matter.url_id.as_deref(), Some("hello"));
+        assert_eq!(
+            front_matter.tags,
+            Some(vec!["rust".into(), "web-dev".into()])
+        );
+        assert_eq!(
+            front_matter.publish_at.unwrap().to_rfc3339(),
+            "2024-03-01T12:00:00+00:00"
+        );
+        assert_eq!(front_matter.summary.as_deref(), Some("Short."));
+        assert_eq!(front_matter.draft, Some(true));
+    }
+
+    #[test]
+    fn parses_toml_front_matter() {
+        let markdown = "+++\ntitle = \"Hello\"\ntags = [\"rust\"]\ndate = 2024-03-01\n+++\nBody";
+
+        let (front_matter, body) = parse(markdown).unwrap().unwrap();
+
+        assert_eq!(body, "Body");
+        assert_eq!(front_matter.title.as_deref(), Some("Hello"));
+        assert_eq!(front_matter.tags, Some(vec!["rust".into()]));
+        assert_eq!(
+            front_matter.publish_at.unwrap().to_rfc3339(),
+            "2024-03-01T00:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn comma_separated_tags_and_unknown_fields_are_accepted() {
+        let markdown = "---\ntags: Rust, web dev\nlayout: post\n---\nBody";
+
+        let (front_matter, _) = parse(markdown).unwrap().unwrap();
+
+        assert_eq!(
+            front_matter.tags,
+            Some(vec!["rust".into(), "web-dev".into()])
+        );
+    }
+
+    #[test]
+    fn markdown_without_front_matter_is_unchanged() {
+        assert!(parse("# Title\n\n---\n\ntext").unwrap().is_none());
+        assert!(parse("---\n\nJust a thematic break.\n\n---\n")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn invalid_front_matter_is_an_error() {
+        assert!(parse("+++\ntitle = \n+++\nBody").is_err());
+        assert!(parse("---\ndraft: maybe\n---\nBody").is_err());
+        assert!(parse("---\ndate: next tuesday\n---\nBody").is_err());
+    }
+}
@@ -0,0 +1,167 @@
+use anyhow::{anyhow, Context};
+use std::path::PathBuf;
+use syntect::highlighting::{Theme, ThemeSet};
+use syntect::html::{css_for_theme_with_class_style, ClassStyle};
+use syntect::parsing::{SyntaxSet, SyntaxSetBuilder};
+
+/// Where to find extra syntax definitions and themes, and which themes to
+/// color code blocks with.
+#[derive(serde::Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct HighlightingSettings {
+    /// A directory of `.sublime-syntax` and `.tmTheme` files, loaded on top
+    /// of the built-in syntaxes and themes.
+    pub directory: Option<PathBuf>,
+    /// The name of a theme, i.e. the file name of a `.tmTheme` file without
+    /// the extension, or a built-in theme such as "base16-ocean.light".
+    pub light_theme: String,
+    pub dark_theme: String,
+}
+
+impl Default for HighlightingSettings {
+    fn default() -> Self {
+        Self {
+            directory: None,
+            light_theme: "InspiredGitHub".to_string(),
+            dark_theme: "Solarized (dark)".to_string(),
+        }
+    }
+}
+
+/// The syntaxes to highlight code blocks with and the CSS for the classes
+/// of the highlighted code.
+#[derive(Debug)]
+pub struct SyntaxHighlighting {
+    pub(crate) syntax_set: SyntaxSet,
+    light_css: String,
+    dark_css: String,
+}
+
+impl SyntaxHighlighting {
+    pub fn load(settings: &HighlightingSettings) -> anyhow::Result<Self> {
+        let mut syntax_set = SyntaxSet::load_defaults_newlines();
+        let mut theme_set = ThemeSet::load_defaults();
+
+        if let Some(directory) = &settings.directory {
+            let mut extra_syntaxes = SyntaxSetBuilder::new();
+            extra_syntaxes
+                .add_from_folder(directory, true)
+                .with_context(|| format!("Failed to load syntaxes from {}", directory.display()))?;
+            // Building a syntax set is slow, so only do it when there is
+            // something to add to the built-in one.
+            if !extra_syntaxes.syntaxes().is_empty() {
+                let mut builder = syntax_set.into_builder();
+                for syntax in extra_syntaxes.syntaxes() {
+                    builder.add(syntax.clone());
+                }
+                syntax_set = builder.build();
+            }
+            theme_set
+                .add_from_folder(directory)
+                .with_context(|| format!("Failed to load themes from {}", directory.display()))?;
+        }
+
+        let light_theme = find_theme(&theme_set, &settings.light_theme)?;
+        let dark_theme = find_theme(&theme_set, &settings.dark_theme)?;
+        Ok(Self {
+            syntax_set,
+            light_css: theme_css(
+                light_theme,
+                "(prefers-color-scheme: no-preference), (prefers-color-scheme: light)",
+            )?,
+            dark_css: theme_css(dark_theme, "(prefers-color-scheme: dark)")?,
+        })
+    }
+
+    /// Applies when the reader prefers a light color scheme.
+    pub fn light_css(&self) -> &str {
+        &self.light_css
+    }
+
+    /// Applies when the reader prefers a dark color scheme.
+    pub fn dark_css(&self) -> &str {
+        &self.dark_css
+    }
+}
+
+fn find_theme<'a>(theme_set: &'a ThemeSet, name: &str) -> anyhow::Result<&'a Theme> {
+    theme_set.themes.get(name).ok_or_else(|| {
+        let mut names = theme_set.themes.keys().cloned().collect::<Vec<_>>();
+        names.sort();
+        anyhow!(
+            "Unknown theme {name:?}, expected one of: {}",
+            names.join(", ")
+        )
+    })
+}
+
+fn theme_css(theme: &Theme, media_query: &str) -> anyhow::Result<String> {
+    let css = css_for_theme_with_class_style(theme, ClassStyle::Spaced)
+        .context("Failed to generate CSS for theme")?;
+    let rules = css
+        .lines()
+        .map(|line| format!(" {line}\n"))
+        .collect::<String>();
+    Ok(format!("@media {media_query} {{\n{rules}}}\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loads_syntaxes_and_themes_from_directory() {
+        let directory =
+            std::env::temp_dir().join(format!("mimir-highlighting-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(
+            directory.join("ini-test.sublime-syntax"),
+            "%YAML 1.2\n---\nname: Ini Test\nfile_extensions: [initest]\nscope: source.initest\n\
+             contexts:\n  main:\n    - match: '^\\[.*\\]$'\n      scope: entity.name.section.initest\n",
+        )
+        .unwrap();
+        std::fs::write(
+            directory.join("Plain.tmTheme"),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0"><dict>
+<key>name</key><string>Plain</string>
+<key>settings</key><array>
+<dict><key>settings</key><dict><key>foreground</key><string>#123456</string><key>background</key><string>#fefefe</string></dict></dict>
+<dict><key>scope</key><string>entity.name.section</string><key>settings</key><dict><key>foreground</key><string>#abcdef</string></dict></dict>
+</array></dict></plist>"#,
+        )
+        .unwrap();
+        let settings = HighlightingSettings {
+            directory: Some(directory.clone()),
+            dark_theme: "Plain".to_string(),
+            ..HighlightingSettings::default()
+        };
+
+        let highlighting = SyntaxHighlighting::load(&settings);
+        std::fs::remove_dir_all(&directory).unwrap();
+        let highlighting = highlighting.unwrap();
+
+        assert!(highlighting
+            .syntax_set
+            .find_syntax_by_token("initest")
+            .is_some());
+        assert!(highlighting
+            .dark_css()
+            .starts_with("@media (prefers-color-scheme: dark) {\n"));
+        assert!(highlighting.dark_css().contains("color: #abcdef;"));
+        assert!(highlighting.light_css().contains("theme \"GitHub\""));
+    }
+
+    #[test]
+    fn unknown_theme_is_an_error() {
+        let settings = HighlightingSettings {
+            light_theme: "Nope".to_string(),
+            ..HighlightingSettings::default()
+        };
+
+        let error = SyntaxHighlighting::load(&settings).unwrap_err();
+
+        assert!(error.to_string().starts_with("Unknown theme \"Nope\""));
+    }
+}
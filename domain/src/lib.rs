@@ -1,16 +1,28 @@
 mod blog;
+mod code_block;
 mod domain_error;
+mod front_matter;
+mod highlighting;
 mod hmac_secret;
 pub(crate) mod markdown;
+mod math;
 mod password;
 mod repository;
+mod revision;
 mod use_cases;
 pub(crate) mod util;
 
-pub use blog::{BlogPost, BlogPostStatus, NewBlogPostData};
+pub use blog::{
+    ArchiveMonth, ArchiveYear, BlogPost, BlogPostStatus, NewBlogPostData, PostOrder, PostQuery,
+    PostSeries, PostStatusFilter, PostSummary, PostsPage, RenderedPost, SearchResult, Series,
+    SnippetFragment, Tag,
+};
 pub use domain_error::DomainError;
+pub use highlighting::{HighlightingSettings, SyntaxHighlighting};
 pub use hmac_secret::HmacSecret;
+pub use markdown::{MarkdownSettings, TocEntry};
 pub use password::Password;
 pub use repository::Repository;
+pub use revision::{DiffLine, PostRevision, RevisionDiff};
 pub use use_cases::admin_use_case::AdminUseCase;
 pub use use_cases::reader_use_case::ReaderUseCase;
@@ -1,23 +1,361 @@
+use crate::blog::slugify;
+use crate::code_block::CodeBlockInfo;
+use crate::math::Formulas;
+use crate::util::escape_html;
 use pulldown_cmark::{CodeBlockKind, CowStr, Event, Options, Parser, Tag, TagEnd};
-use std::sync::OnceLock;
+use std::collections::HashSet;
 use syntect::html::{ClassStyle, ClassedHTMLGenerator};
 use syntect::parsing::SyntaxSet;
 use syntect::util::LinesWithEndings;
 
-pub fn convert_to_html(markdown: &str) -> anyhow::Result<String> {
-    static SS: OnceLock<SyntaxSet> = OnceLock::new();
+/// The markdown extensions to enable, on top of CommonMark. By default the
+/// ones of GitHub-flavoured markdown are enabled, as well as math.
+#[derive(serde::Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct MarkdownSettings {
+    pub footnotes: bool,
+    pub tables: bool,
+    pub strikethrough: bool,
+    pub tasklists: bool,
+    pub smart_punctuation: bool,
+    /// E.g. `# Heading {#custom-id .class}`
+    pub heading_attributes: bool,
+    /// LaTeX math between `$…$` or `$$…$$`, rendered to MathML.
+    pub math: bool,
+}
+
+impl Default for MarkdownSettings {
+    fn default() -> Self {
+        Self {
+            footnotes: true,
+            tables: true,
+            strikethrough: true,
+            tasklists: true,
+            smart_punctuation: false,
+            heading_attributes: false,
+            math: true,
+        }
+    }
+}
+
+impl MarkdownSettings {
+    fn options(&self) -> Options {
+        [
+            (self.footnotes, Options::ENABLE_FOOTNOTES),
+            (self.tables, Options::ENABLE_TABLES),
+            (self.strikethrough, Options::ENABLE_STRIKETHROUGH),
+            (self.tasklists, Options::ENABLE_TASKLISTS),
+            (self.smart_punctuation, Options::ENABLE_SMART_PUNCTUATION),
+            (self.heading_attributes, Options::ENABLE_HEADING_ATTRIBUTES),
+        ]
+        .into_iter()
+        .filter(|(enabled, _)| *enabled)
+        .fold(Options::empty(), |options, (_, option)| options | option)
+    }
 
-    let syntax_set = SS.get_or_init(SyntaxSet::load_defaults_newlines);
+    fn extract_formulas(&self, markdown: &str) -> (String, Formulas) {
+        if self.math {
+            Formulas::extract(markdown, self.options())
+        } else {
+            (markdown.to_string(), Formulas::default())
+        }
+    }
+}
+
+pub fn convert_to_html(
+    markdown: &str,
+    settings: &MarkdownSettings,
+    syntax_set: &SyntaxSet,
+) -> anyhow::Result<String> {
+    render_html(markdown, settings, syntax_set, true)
+}
+
+/// Like [`convert_to_html`], but without heading ids and anchors, since
+/// excerpts of many posts are shown on the same page.
+pub fn convert_excerpt_to_html(
+    markdown: &str,
+    settings: &MarkdownSettings,
+    syntax_set: &SyntaxSet,
+) -> anyhow::Result<String> {
+    render_html(markdown, settings, syntax_set, false)
+}
 
-    let parser = Parser::new_ext(markdown, Options::ENABLE_FOOTNOTES | Options::ENABLE_TABLES);
+fn render_html(
+    markdown: &str,
+    settings: &MarkdownSettings,
+    syntax_set: &SyntaxSet,
+    heading_anchors: bool,
+) -> anyhow::Result<String> {
+    let (markdown, formulas) = settings.extract_formulas(markdown);
+    let parser = Parser::new_ext(&markdown, settings.options());
 
-    let hl = highlight(parser, syntax_set)?;
+    let events = remove_toc_marker(highlight(parser, syntax_set)?);
+    let events = if heading_anchors {
+        anchor_headings(events, &formulas)
+    } else {
+        remove_heading_ids(events)
+    };
+    let events = formulas.insert(admonitions(events));
 
     let mut html_output = String::new();
-    pulldown_cmark::html::push_html(&mut html_output, hl.into_iter());
+    pulldown_cmark::html::push_html(&mut html_output, events.into_iter());
     Ok(html_output)
 }
 
+/// Marks where a post wants its table of contents.
+const TOC_MARKER: &str = "[TOC]";
+
+/// A heading in the table of contents of a post.
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+pub struct TocEntry {
+    /// 1 to 6.
+    pub level: u8,
+    /// The id of the heading element, to link to.
+    pub id: String,
+    pub title: String,
+}
+
+/// The headings of the markdown, if it contains a `[TOC]` paragraph.
+pub fn table_of_contents(markdown: &str, settings: &MarkdownSettings) -> Option<Vec<TocEntry>> {
+    let (markdown, formulas) = settings.extract_formulas(markdown);
+    let events = Parser::new_ext(&markdown, settings.options()).collect::<Vec<_>>();
+    if !(0..events.len()).any(|i| toc_marker_len(&events[i..]).is_some()) {
+        return None;
+    }
+
+    let mut ids = HeadingIds::default();
+    let mut entries = Vec::new();
+    let mut events = events.into_iter();
+    while let Some(event) = events.next() {
+        if let Event::Start(Tag::Heading { level, id, .. }) = event {
+            let title = heading_text(events.by_ref().take_while(|e| !is_heading_end(e)));
+            let title = formulas.restore(&title);
+            entries.push(TocEntry {
+                level: level as u8,
+                id: ids.unique(id.as_deref(), &title),
+                title,
+            });
+        }
+    }
+    Some(entries)
+}
+
+/// Gives every heading a unique id, unless it already has one, and appends
+/// a link to the heading itself. Formulas count with their LaTeX source.
+fn anchor_headings<'a>(events: Vec<Event<'a>>, formulas: &Formulas) -> Vec<Event<'a>> {
+    let mut ids = HeadingIds::default();
+    let mut out_events = Vec::with_capacity(events.len());
+    let mut events = events.into_iter();
+
+    while let Some(event) = events.next() {
+        let Event::Start(Tag::Heading {
+            level,
+            id,
+            classes,
+            attrs,
+        }) = event
+        else {
+            out_events.push(event);
+            continue;
+        };
+
+        let content = events
+            .by_ref()
+            .take_while(|e| !is_heading_end(e))
+            .collect::<Vec<_>>();
+        let title = formulas.restore(&heading_text(content.iter().cloned()));
+        let id = ids.unique(id.as_deref(), &title);
+        let anchor = format!(
+            r##"<a class="heading-anchor" href="#{}" aria-label="Link to this section">#</a>"##,
+            escape_html(&id)
+        );
+
+        out_events.push(Event::Start(Tag::Heading {
+            level,
+            id: Some(CowStr::from(id)),
+            classes,
+            attrs,
+        }));
+        out_events.extend(content);
+        out_events.push(Event::Html(CowStr::from(anchor)));
+        out_events.push(Event::End(TagEnd::Heading(level)));
+    }
+    out_events
+}
+
+fn remove_heading_ids(events: Vec<Event<'_>>) -> Vec<Event<'_>> {
+    events
+        .into_iter()
+        .map(|event| match event {
+            Event::Start(Tag::Heading {
+                level,
+                classes,
+                attrs,
+                ..
+            }) => Event::Start(Tag::Heading {
+                level,
+                id: None,
+                classes,
+                attrs,
+            }),
+            event => event,
+        })
+        .collect()
+}
+
+fn remove_toc_marker(events: Vec<Event<'_>>) -> Vec<Event<'_>> {
+    let mut out_events = Vec::with_capacity(events.len());
+    let mut i = 0;
+    while i < events.len() {
+        if let Some(len) = toc_marker_len(&events[i..]) {
+            i += len;
+        } else {
+            out_events.push(events[i].clone());
+            i += 1;
+        }
+    }
+    out_events
+}
+
+/// The kinds of GitHub-style callouts, e.g. `> [!NOTE]`.
+const ADMONITIONS: [&str; 5] = ["note", "tip", "important", "warning", "caution"];
+
+/// Turns block quotes that start with an admonition marker into callouts.
+fn admonitions(events: Vec<Event<'_>>) -> Vec<Event<'_>> {
+    let mut out_events = Vec::with_capacity(events.len());
+    // Whether each open block quote is an admonition.
+    let mut open = Vec::new();
+    let mut i = 0;
+    while i < events.len() {
+        match &events[i] {
+            Event::Start(Tag::BlockQuote) => match admonition_marker(&events[i + 1..]) {
+                Some((kind, len, more_text)) => {
+                    out_events.push(Event::Html(CowStr::from(format!(
+                        "<aside class=\"admonition admonition-{kind}\">\n<p class=\"admonition-title\">{}</p>\n",
+                        capitalize(kind)
+                    ))));
+                    if more_text {
+                        out_events.push(Event::Start(Tag::Paragraph));
+                    }
+                    open.push(true);
+                    i += len + 1;
+                    continue;
+                }
+                None => open.push(false),
+            },
+            Event::End(TagEnd::BlockQuote) if open.pop() == Some(true) => {
+                out_events.push(Event::Html(CowStr::from("</aside>\n")));
+                i += 1;
+                continue;
+            }
+            _ => {}
+        }
+        out_events.push(events[i].clone());
+        i += 1;
+    }
+    out_events
+}
+
+// The kind of the `[!KIND]` line at the start of a block quote, the number
+// of its events and whether the paragraph continues after it.
+fn admonition_marker(events: &[Event<'_>]) -> Option<(&'static str, usize, bool)> {
+    let [Event::Start(Tag::Paragraph), rest @ ..] = events else {
+        return None;
+    };
+
+    let mut text = String::new();
+    for (i, event) in rest.iter().enumerate() {
+        let more_text = match event {
+            Event::Text(t) => {
+                text.push_str(t);
+                continue;
+            }
+            Event::SoftBreak | Event::HardBreak => true,
+            Event::End(TagEnd::Paragraph) => false,
+            _ => return None,
+        };
+        return Some((admonition_kind(&text)?, i + 2, more_text));
+    }
+    None
+}
+
+// The kind of an admonition marker such as `[!NOTE]`.
+fn admonition_kind(text: &str) -> Option<&'static str> {
+    let kind = text
+        .trim()
+        .strip_prefix("[!")?
+        .strip_suffix(']')?
+        .to_lowercase();
+    ADMONITIONS.into_iter().find(|k| *k == kind)
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .map(|c| c.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+// The number of events of the paragraph at the start of `events` if it only
+// contains the marker. The parser may split the marker into several texts.
+fn toc_marker_len(events: &[Event<'_>]) -> Option<usize> {
+    let [Event::Start(Tag::Paragraph), rest @ ..] = events else {
+        return None;
+    };
+
+    let mut text = String::new();
+    for (i, event) in rest.iter().enumerate() {
+        match event {
+            Event::Text(t) => text.push_str(t),
+            Event::End(TagEnd::Paragraph) if text.trim() == TOC_MARKER => return Some(i + 2),
+            _ => return None,
+        }
+    }
+    None
+}
+
+fn is_heading_end(event: &Event<'_>) -> bool {
+    matches!(event, Event::End(TagEnd::Heading(_)))
+}
+
+fn heading_text<'a>(events: impl Iterator<Item = Event<'a>>) -> String {
+    events
+        .filter_map(|event| match event {
+            Event::Text(t) | Event::Code(t) => Some(t),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .concat()
+}
+
+/// Heading ids that are unique within a post and stay the same as long as
+/// the headings don't change.
+#[derive(Default)]
+struct HeadingIds {
+    used: HashSet<String>,
+}
+
+impl HeadingIds {
+    fn unique(&mut self, explicit_id: Option<&str>, title: &str) -> String {
+        let mut slug = match explicit_id {
+            Some(id) => id.to_string(),
+            None => slugify(title),
+        };
+        if slug.is_empty() {
+            slug = "section".to_string();
+        }
+        let mut id = slug.clone();
+        let mut n = 1;
+        while self.used.contains(&id) {
+            id = format!("{slug}-{n}");
+            n += 1;
+        }
+        self.used.insert(id.clone());
+        id
+    }
+}
+
 fn highlight<'a, I>(events: I, syntax_set: &SyntaxSet) -> Result<Vec<Event<'a>>, anyhow::Error>
 where
     I: Iterator<Item = Event<'a>>,
@@ -28,25 +366,26 @@ where
     let mut out_events = Vec::new();
 
     let mut syntax = syntax_set.find_syntax_plain_text();
+    let mut info = CodeBlockInfo::default();
 
     for event in events {
         match event {
             Event::Start(Tag::CodeBlock(kind)) => {
                 match kind {
-                    CodeBlockKind::Fenced(lang) => {
+                    CodeBlockKind::Fenced(info_string) => {
+                        info = CodeBlockInfo::parse(&info_string);
                         syntax = syntax_set
-                            .find_syntax_by_token(&lang)
+                            .find_syntax_by_token(&info.language)
                             .unwrap_or_else(|| syntax_set.find_syntax_plain_text());
                     }
                     CodeBlockKind::Indented => {
+                        info = CodeBlockInfo::default();
                         syntax = syntax_set.find_syntax_plain_text();
                     }
                 }
                 in_code_block = true;
             }
             Event::End(TagEnd::CodeBlock) => {
-                let mut html = "<pre><code>".to_string();
-
                 let mut html_generator = ClassedHTMLGenerator::new_with_class_style(
                     syntax,
                     syntax_set,
@@ -56,9 +395,7 @@ where
                 for line in LinesWithEndings::from(&to_highlight) {
                     html_generator.parse_html_for_line_which_includes_newline(line)?;
                 }
-                html.push_str(html_generator.finalize().as_str());
-
-                html.push_str("</code></pre>");
+                let html = info.render(&html_generator.finalize());
 
                 to_highlight.clear();
                 in_code_block = false;
@@ -79,20 +416,262 @@ where
     Ok(out_events)
 }
 
+/// Marks the end of the excerpt in a post.
+const MORE_MARKER: &str = "<!-- more -->";
+
+/// The markdown before the `<!-- more -->` marker, or the first paragraph
+/// if there is no marker.
+pub fn excerpt<'a>(markdown: &'a str, settings: &MarkdownSettings) -> &'a str {
+    // Only a marker that is HTML counts, not one in e.g. a code block.
+    let marker_start = Parser::new_ext(markdown, settings.options())
+        .into_offset_iter()
+        .find_map(|(event, range)| match event {
+            Event::Html(html) | Event::InlineHtml(html) if html.trim() == MORE_MARKER => {
+                Some(range.start)
+            }
+            _ => None,
+        });
+    if let Some(marker_start) = marker_start {
+        return markdown[..marker_start].trim_end();
+    }
+
+    // A paragraph in a block quote, e.g. in an admonition, takes the whole
+    // quote with it.
+    let mut quote_start = None;
+    let mut quote_depth = 0;
+    let mut quote_has_text = false;
+    let mut start = None;
+    for (event, range) in Parser::new_ext(markdown, settings.options()).into_offset_iter() {
+        match event {
+            Event::Start(Tag::BlockQuote) => {
+                quote_depth += 1;
+                quote_start.get_or_insert(range.start);
+            }
+            Event::End(TagEnd::BlockQuote) => {
+                quote_depth -= 1;
+                if quote_depth == 0 {
+                    let quote_start = quote_start.take().unwrap_or(range.start);
+                    if quote_has_text {
+                        return markdown[quote_start..range.end].trim_end();
+                    }
+                }
+            }
+            Event::Start(Tag::Paragraph) => start = Some(range.start),
+            Event::End(TagEnd::Paragraph) => {
+                let paragraph = markdown[start.unwrap_or(range.start)..range.end].trim_end();
+                if paragraph.trim().is_empty() || paragraph.trim() == TOC_MARKER {
+                    continue;
+                }
+                if quote_depth == 0 {
+                    return paragraph;
+                }
+                quote_has_text = true;
+            }
+            _ => {}
+        }
+    }
+    ""
+}
+
+/// Number of words in the text of the markdown, not counting code blocks.
+pub fn word_count(markdown: &str, settings: &MarkdownSettings) -> usize {
+    let mut in_code_block = false;
+    let mut count = 0;
+
+    for event in Parser::new_ext(markdown, settings.options()) {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(TagEnd::CodeBlock) => in_code_block = false,
+            Event::Text(t) | Event::Code(t) if !in_code_block => {
+                count += t.split_whitespace().count()
+            }
+            _ => {}
+        }
+    }
+    count
+}
+
+/// The URL of the first image in the markdown.
+pub fn first_image_url(markdown: &str, settings: &MarkdownSettings) -> Option<String> {
+    Parser::new_ext(markdown, settings.options()).find_map(|event| match event {
+        Event::Start(Tag::Image { dest_url, .. }) => Some(dest_url.to_string()),
+        _ => None,
+    })
+}
+
+/// The plain text of the first paragraph, shortened to at most `max_chars`
+/// characters at a word boundary, or within the first word if that is longer.
+pub fn plain_text_excerpt(markdown: &str, settings: &MarkdownSettings, max_chars: usize) -> String {
+    let mut text = String::new();
+    let mut in_paragraph = false;
+    let mut in_image = false;
+
+    for event in Parser::new_ext(markdown, settings.options()) {
+        match event {
+            Event::Start(Tag::Paragraph) => in_paragraph = true,
+            Event::End(TagEnd::Paragraph)
+                if text.trim() == TOC_MARKER || admonition_kind(&text).is_some() =>
+            {
+                text.clear();
+                in_paragraph = false;
+            }
+            Event::End(TagEnd::Paragraph) if !text.trim().is_empty() => break,
+            Event::End(TagEnd::Paragraph) => in_paragraph = false,
+            Event::Start(Tag::Image { .. }) => in_image = true,
+            Event::End(TagEnd::Image) => in_image = false,
+            Event::Text(t) | Event::Code(t) if in_paragraph && !in_image => text.push_str(&t),
+            Event::SoftBreak | Event::HardBreak if admonition_kind(&text).is_some() => {
+                text.clear();
+            }
+            Event::SoftBreak | Event::HardBreak if in_paragraph => text.push(' '),
+            _ => {}
+        }
+    }
+
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() <= max_chars {
+        return text;
+    }
+
+    let mut excerpt = String::new();
+    for word in text.split(' ') {
+        if excerpt.is_empty() && word.chars().count() > max_chars {
+            excerpt.extend(word.chars().take(max_chars));
+            break;
+        }
+        if excerpt.chars().count() + word.chars().count() + 1 > max_chars {
+            break;
+        }
+        if !excerpt.is_empty() {
+            excerpt.push(' ');
+        }
+        excerpt.push_str(word);
+    }
+    excerpt.push('…');
+    excerpt
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use claim::assert_ok;
+    use std::sync::OnceLock;
+
+    fn to_html(markdown: &str, settings: &MarkdownSettings) -> anyhow::Result<String> {
+        static SS: OnceLock<SyntaxSet> = OnceLock::new();
+        convert_to_html(
+            markdown,
+            settings,
+            SS.get_or_init(SyntaxSet::load_defaults_newlines),
+        )
+    }
+
+    #[test]
+    fn headings_get_unique_ids_and_anchors() {
+        let html = to_html(
+            "# Intro\n\n## Set `up`\n\n## Intro",
+            &MarkdownSettings::default(),
+        )
+        .unwrap();
+        assert!(html.contains(r##"<h1 id="intro">Intro<a class="heading-anchor" href="#intro""##));
+        assert!(html.contains(r#"<h2 id="set-up">Set <code>up</code>"#));
+        assert!(html.contains(r#"<h2 id="intro-1">"#));
+    }
+
+    #[test]
+    fn excerpt_headings_get_no_ids_or_anchors() {
+        let settings = MarkdownSettings {
+            heading_attributes: true,
+            ..MarkdownSettings::default()
+        };
+        let syntax_set = SyntaxSet::load_defaults_newlines();
+
+        let html =
+            convert_excerpt_to_html("# Intro\n\n## Custom {#custom}", &settings, &syntax_set)
+                .unwrap();
+
+        assert_eq!(html, "<h1>Intro</h1>\n<h2>Custom</h2>\n");
+    }
+
+    #[test]
+    fn table_of_contents_needs_marker() {
+        assert_eq!(
+            table_of_contents("# Intro\n\nText", &MarkdownSettings::default()),
+            None
+        );
+    }
+
+    #[test]
+    fn table_of_contents_matches_heading_ids() {
+        let markdown = "[TOC]\n\n# Intro\n\n## Intro\n\ntext";
+        let toc = table_of_contents(markdown, &MarkdownSettings::default()).unwrap();
+        assert_eq!(
+            toc,
+            vec![
+                TocEntry {
+                    level: 1,
+                    id: "intro".to_string(),
+                    title: "Intro".to_string()
+                },
+                TocEntry {
+                    level: 2,
+                    id: "intro-1".to_string(),
+                    title: "Intro".to_string()
+                },
+            ]
+        );
+
+        let html = to_html(markdown, &MarkdownSettings::default()).unwrap();
+        assert!(!html.contains("[TOC]"));
+        assert!(html.contains(r#"<h2 id="intro-1">"#));
+    }
+
+    #[test]
+    fn word_count_skips_code_blocks() {
+        let markdown = "# A title\n\nSome *emphasized* `code` words.\n\n```rust\nlet a = 1;\n```\n";
+        assert_eq!(word_count(markdown, &MarkdownSettings::default()), 6);
+    }
+
+    #[test]
+    fn excerpt_ends_at_more_marker() {
+        let markdown = "First *paragraph*.\n\nSecond.\n\n<!-- more -->\n\nRest.";
+        assert_eq!(
+            excerpt(markdown, &MarkdownSettings::default()),
+            "First *paragraph*.\n\nSecond."
+        );
+    }
+
+    #[test]
+    fn excerpt_ignores_more_marker_in_code() {
+        let markdown = "Intro.\n\n```html\n<!-- more -->\n```\n\nUse `<!-- more -->`.";
+        assert_eq!(excerpt(markdown, &MarkdownSettings::default()), "Intro.");
+
+        let markdown = "Intro `<!-- more -->` here.\n\n```\n<!-- more -->\n```\n\n<!-- more -->";
+        assert_eq!(
+            excerpt(markdown, &MarkdownSettings::default()),
+            "Intro `<!-- more -->` here.\n\n```\n<!-- more -->\n```"
+        );
+    }
+
+    #[test]
+    fn excerpt_without_marker_is_first_paragraph() {
+        let markdown = "# Title\n\nFirst *paragraph*\nwith two lines.\n\nSecond.";
+        assert_eq!(
+            excerpt(markdown, &MarkdownSettings::default()),
+            "First *paragraph*\nwith two lines."
+        );
+    }
 
     #[test]
     fn test_highlight_output() {
-        let output = convert_to_html(
+        let output = to_html(
             r#"
 ```rust
 let k = "k".to_string();
 let a = 12;
 ```
 "#,
+            &MarkdownSettings::default(),
         );
         assert_ok!(output, "<pre><code><span class=\"source rust\">\
         <span class=\"storage type rust\">let</span> k <span class=\"keyword operator rust\">=</span> \
@@ -105,4 +684,205 @@ let a = 12;
         <span class=\"keyword operator rust\">=</span> <span class=\"constant numeric integer decimal rust\">12\
         </span><span class=\"punctuation terminator rust\">;</span>\n</span></code></pre>");
     }
+
+    #[test]
+    fn extensions_can_be_toggled() {
+        let markdown = "~~gone~~ \"quoted\"";
+        let settings = MarkdownSettings {
+            strikethrough: false,
+            smart_punctuation: true,
+            ..MarkdownSettings::default()
+        };
+
+        assert_eq!(
+            to_html(markdown, &MarkdownSettings::default()).unwrap(),
+            "<p><del>gone</del> \"quoted\"</p>\n"
+        );
+        assert_eq!(
+            to_html(markdown, &settings).unwrap(),
+            "<p>~~gone~~ “quoted”</p>\n"
+        );
+    }
+
+    #[test]
+    fn math_is_rendered_to_mathml() {
+        let markdown = "Let $a_1 = a_2$, not `$b$`.\n\n$$\\sqrt{x}$$\n\nBroken: $\\foo$";
+
+        let html = to_html(markdown, &MarkdownSettings::default()).unwrap();
+
+        assert_eq!(
+            html,
+            "<p>Let <math xmlns=\"http://www.w3.org/1998/Math/MathML\"><mrow><msub><mi>a</mi><mn>1</mn></msub><mo>=</mo><msub><mi>a</mi><mn>2</mn></msub></mrow></math>, not <code>$b$</code>.</p>\n\
+             <p><math xmlns=\"http://www.w3.org/1998/Math/MathML\" display=\"block\"><msqrt><mi>x</mi></msqrt></math></p>\n\
+             <p>Broken: <span class=\"math-error\"><code>$\\foo$</code> <span class=\"math-error-message\">Unknown command \\foo</span></span></p>\n"
+        );
+        let settings = MarkdownSettings {
+            math: false,
+            ..MarkdownSettings::default()
+        };
+        assert_eq!(to_html("$x$", &settings).unwrap(), "<p>$x$</p>\n");
+    }
+
+    #[test]
+    fn headings_with_math_get_ids_from_the_source() {
+        let markdown = "[TOC]\n\n# Euler $e^{i\\pi}$";
+        let settings = MarkdownSettings::default();
+
+        let html = to_html(markdown, &settings).unwrap();
+        let toc = table_of_contents(markdown, &settings).unwrap();
+
+        assert_eq!(toc[0].title, "Euler e^{i\\pi}");
+        assert!(html.starts_with(&format!("<h1 id=\"{}\">Euler <math", toc[0].id)));
+    }
+
+    #[test]
+    fn math_in_alt_text_and_heading_attributes_stays_tex() {
+        let settings = MarkdownSettings {
+            heading_attributes: true,
+            ..MarkdownSettings::default()
+        };
+
+        let html = to_html("# $x$ {#$y$}\n\n![$a$](/a.png)", &settings).unwrap();
+
+        assert!(html.starts_with("<h1 id=\"$y$\"><math"));
+        assert!(html.ends_with("<p><img src=\"/a.png\" alt=\"$a$\" /></p>\n"));
+    }
+
+    #[test]
+    fn marked_block_quotes_become_admonitions() {
+        let markdown =
+            "> [!WARNING]\n> Mind the *gap*.\n\n> [!TIP]\n>\n> - one\n\n> [!FOO]\n> Quote.";
+
+        let html = to_html(markdown, &MarkdownSettings::default()).unwrap();
+
+        assert_eq!(
+            html,
+            "<aside class=\"admonition admonition-warning\">\n\
+             <p class=\"admonition-title\">Warning</p>\n\
+             <p>Mind the <em>gap</em>.</p>\n\
+             </aside>\n\
+             <aside class=\"admonition admonition-tip\">\n\
+             <p class=\"admonition-title\">Tip</p>\n\
+             <ul>\n<li>one</li>\n</ul>\n\
+             </aside>\n\
+             <blockquote>\n<p>[!FOO]\nQuote.</p>\n</blockquote>\n"
+        );
+    }
+
+    #[test]
+    fn excerpts_skip_admonition_markers() {
+        let markdown = "> [!NOTE]\n> Read this.\n\nMore.";
+
+        assert_eq!(
+            excerpt(markdown, &MarkdownSettings::default()),
+            "> [!NOTE]\n> Read this."
+        );
+        assert_eq!(
+            plain_text_excerpt(markdown, &MarkdownSettings::default(), 100),
+            "Read this."
+        );
+    }
+
+    #[test]
+    fn code_block_info_adds_title_line_numbers_and_highlights() {
+        let markdown = "```text title=\"notes.txt\" {2} linenos\none\ntwo\n```";
+
+        let html = to_html(markdown, &MarkdownSettings::default()).unwrap();
+
+        assert_eq!(
+            html,
+            "<figure class=\"code-block\"><figcaption class=\"code-title\">notes.txt</figcaption>\
+             <pre><code class=\"lines\">\
+             <span class=\"line\"><span class=\"line-number\" aria-hidden=\"true\">1</span>\
+             <span class=\"text plain\">one</span>\n</span>\
+             <span class=\"line highlighted\"><span class=\"line-number\" aria-hidden=\"true\">2</span>\
+             <span class=\"text plain\">two</span>\n</span>\
+             </code></pre></figure>"
+        );
+    }
+
+    #[test]
+    fn heading_attributes_set_the_heading_id() {
+        let settings = MarkdownSettings {
+            heading_attributes: true,
+            ..MarkdownSettings::default()
+        };
+
+        let html = to_html("# Title {#custom}", &settings).unwrap();
+
+        assert!(html.starts_with(r#"<h1 id="custom">Title<a"#));
+        assert_eq!(
+            to_html("# Title {#custom}", &MarkdownSettings::default()).unwrap(),
+            "<h1 id=\"title-custom\">Title {#custom}<a class=\"heading-anchor\" href=\"#title-custom\" aria-label=\"Link to this section\">#</a></h1>\n"
+        );
+    }
+
+    #[test]
+    fn explicit_heading_ids_are_escaped_and_made_unique() {
+        let settings = MarkdownSettings {
+            heading_attributes: true,
+            ..MarkdownSettings::default()
+        };
+
+        let html = to_html("# A {#a\"b}\n\n# B {#a\"b}", &settings).unwrap();
+
+        assert_eq!(
+            html,
+            "<h1 id=\"a&quot;b\">A<a class=\"heading-anchor\" href=\"#a&quot;b\" aria-label=\"Link to this section\">#</a></h1>\n\
+             <h1 id=\"a&quot;b-1\">B<a class=\"heading-anchor\" href=\"#a&quot;b-1\" aria-label=\"Link to this section\">#</a></h1>\n"
+        );
+    }
+
+    #[test]
+    fn first_image_url_is_found() {
+        let markdown = "Some text\n\n![a cat](/images/cat.png)\n\n![a dog](/images/dog.png)";
+        assert_eq!(
+            first_image_url(markdown, &MarkdownSettings::default()),
+            Some("/images/cat.png".to_string())
+        );
+        assert_eq!(
+            first_image_url("no images", &MarkdownSettings::default()),
+            None
+        );
+    }
+
+    #[test]
+    fn excerpt_is_the_first_paragraph_as_plain_text() {
+        let markdown =
+            "# Heading\n\nThis is *the* `first`\nparagraph ![alt](/a.png)\n\nSecond paragraph";
+        assert_eq!(
+            plain_text_excerpt(markdown, &MarkdownSettings::default(), 100),
+            "This is the first paragraph"
+        );
+    }
+
+    #[test]
+    fn long_excerpt_is_cut_at_a_word_boundary() {
+        assert_eq!(
+            plain_text_excerpt("one two three four", &MarkdownSettings::default(), 12),
+            "one two…"
+        );
+    }
+
+    #[test]
+    fn long_first_word_is_cut_within_the_word() {
+        assert_eq!(
+            plain_text_excerpt("abcdéfghij klm", &MarkdownSettings::default(), 5),
+            "abcdé…"
+        );
+    }
+
+    #[test]
+    fn helpers_parse_with_the_markdown_settings() {
+        let settings = MarkdownSettings {
+            heading_attributes: true,
+            ..MarkdownSettings::default()
+        };
+        let markdown = "# Title {#custom}
+
+Some text.";
+
+        assert_eq!(word_count(markdown, &settings), 3);
+        assert_eq!(word_count(markdown, &MarkdownSettings::default()), 4);
+    }
 }
@@ -0,0 +1,1185 @@
+use crate::util::escape_html;
+use pulldown_cmark::{CowStr, Event, LinkType, Options, Parser, Tag, TagEnd};
+use std::ops::Range;
+
+// Formulas are swapped for placeholders before the markdown is parsed, so
+// that e.g. the underscores in `$a_1 + b_1$` don't start emphasis. The
+// private use characters never appear in ordinary text.
+const PLACEHOLDER_START: char = '\u{E000}';
+const PLACEHOLDER_END: char = '\u{E001}';
+
+/// The `$inline$` and `$$display$$` formulas of a markdown document.
+#[derive(Default)]
+pub(crate) struct Formulas {
+    formulas: Vec<Formula>,
+}
+
+struct Formula {
+    source: String,
+    display: bool,
+}
+
+impl Formulas {
+    /// Replaces the formulas outside of code, HTML, link destinations, image
+    /// alt texts and heading attribute blocks with placeholders.
+    pub(crate) fn extract(markdown: &str, options: Options) -> (String, Self) {
+        let skip = skipped_ranges(markdown, options);
+
+        let bytes = markdown.as_bytes();
+        let mut out = String::with_capacity(markdown.len());
+        let mut formulas = Vec::new();
+        let mut copied = 0;
+        let mut i = 0;
+        // The first skipped range that doesn't end before `i`
+        let mut next_skip = 0;
+        while i < bytes.len() {
+            while skip.get(next_skip).is_some_and(|r| r.end <= i) {
+                next_skip += 1;
+            }
+            let next_skip_start = skip.get(next_skip).map_or(bytes.len(), |r| r.start);
+            if next_skip_start <= i {
+                i = skip[next_skip].end;
+                continue;
+            }
+            match bytes[i] {
+                b'\\' => i += 2,
+                b'$' => {
+                    let display = bytes.get(i + 1) == Some(&b'$');
+                    let found = if display {
+                        find_display(markdown, i)
+                    } else {
+                        find_inline(markdown, i)
+                    };
+                    match found.filter(|(_, end)| *end <= next_skip_start) {
+                        Some((source, end)) => {
+                            out.push_str(&markdown[copied..i]);
+                            out.push(PLACEHOLDER_START);
+                            out.push_str(&formulas.len().to_string());
+                            out.push(PLACEHOLDER_END);
+                            formulas.push(Formula {
+                                source: source.trim().to_string(),
+                                display,
+                            });
+                            copied = end;
+                            i = end;
+                        }
+                        None => i += if display { 2 } else { 1 },
+                    }
+                }
+                _ => i += 1,
+            }
+        }
+
+        if formulas.is_empty() {
+            return (markdown.to_string(), Self::default());
+        }
+        out.push_str(&markdown[copied..]);
+        (out, Self { formulas })
+    }
+
+    /// Replaces the placeholders in the text events with MathML, or with an
+    /// error message for the formulas that can't be converted.
+    pub(crate) fn insert<'a>(&self, events: Vec<Event<'a>>) -> Vec<Event<'a>> {
+        if self.formulas.is_empty() {
+            return events;
+        }
+
+        let mut out_events = Vec::with_capacity(events.len());
+        for event in events {
+            let Event::Text(text) = event else {
+                out_events.push(event);
+                continue;
+            };
+            let mut rest = &*text;
+            while let Some((before, formula, after)) = self.next_placeholder(rest) {
+                if !before.is_empty() {
+                    out_events.push(Event::Text(CowStr::from(before.to_string())));
+                }
+                out_events.push(Event::InlineHtml(CowStr::from(formula.to_html())));
+                rest = after;
+            }
+            if rest.len() == text.len() {
+                out_events.push(Event::Text(text));
+            } else if !rest.is_empty() {
+                out_events.push(Event::Text(CowStr::from(rest.to_string())));
+            }
+        }
+        out_events
+    }
+
+    /// The text with the placeholders replaced by the LaTeX source.
+    pub(crate) fn restore(&self, text: &str) -> String {
+        let mut restored = String::new();
+        let mut rest = text;
+        while let Some((before, formula, after)) = self.next_placeholder(rest) {
+            restored.push_str(before);
+            restored.push_str(&formula.source);
+            rest = after;
+        }
+        restored.push_str(rest);
+        restored
+    }
+
+    fn next_placeholder<'t>(&self, text: &'t str) -> Option<(&'t str, &Formula, &'t str)> {
+        let start = text.find(PLACEHOLDER_START)?;
+        let index_start = start + PLACEHOLDER_START.len_utf8();
+        let index_len = text[index_start..].find(PLACEHOLDER_END)?;
+        let index = text[index_start..index_start + index_len]
+            .parse::<usize>()
+            .ok()?;
+        let end = index_start + index_len + PLACEHOLDER_END.len_utf8();
+        Some((&text[..start], self.formulas.get(index)?, &text[end..]))
+    }
+}
+
+impl Formula {
+    fn to_html(&self) -> String {
+        match to_mathml(&self.source, self.display) {
+            Ok(mathml) => mathml,
+            Err(error) => {
+                let delimiter = if self.display { "$$" } else { "$" };
+                format!(
+                    r#"<span class="math-error"><code>{delimiter}{}{delimiter}</code> <span class="math-error-message">{}</span></span>"#,
+                    escape_html(&self.source),
+                    escape_html(&error)
+                )
+            }
+        }
+    }
+}
+
+// The ranges of code, HTML, autolinks and the destinations of links, images
+// and link reference definitions, sorted by their start.
+fn skipped_ranges(markdown: &str, options: Options) -> Vec<Range<usize>> {
+    let mut parser = Parser::new_ext(markdown, options).into_offset_iter();
+    let mut ranges = Vec::new();
+    // Whether all of each open link or image is skipped, as autolinks and the
+    // alt texts of images are, and where its text ends so far. The rest of it
+    // is the destination, e.g. `](url "title")`.
+    let mut open_links = Vec::<(bool, usize)>::new();
+    // Where the text of the open heading ends so far. The rest of it is the
+    // `{#id .class}` attribute block, if the option is on.
+    let mut heading_text_end = None;
+    for (event, range) in parser.by_ref() {
+        match event {
+            Event::Start(Tag::Heading { .. }) => heading_text_end = Some(range.start),
+            Event::End(TagEnd::Heading(_)) => {
+                if let Some(text_end) = heading_text_end.take() {
+                    if options.contains(Options::ENABLE_HEADING_ATTRIBUTES) {
+                        ranges.push(text_end..range.end);
+                    }
+                }
+            }
+            _ => {
+                if let Some(text_end) = heading_text_end.as_mut() {
+                    *text_end = (*text_end).max(range.end);
+                }
+            }
+        }
+        match event {
+            Event::Start(Tag::CodeBlock(_) | Tag::HtmlBlock)
+            | Event::Code(_)
+            | Event::Html(_)
+            | Event::InlineHtml(_) => ranges.push(range.clone()),
+            _ => {}
+        }
+        match event {
+            Event::Start(Tag::Link { link_type, .. }) => {
+                let autolink = matches!(link_type, LinkType::Autolink | LinkType::Email);
+                open_links.push((autolink, range.start));
+            }
+            Event::Start(Tag::Image { .. }) => open_links.push((true, range.start)),
+            Event::End(TagEnd::Link | TagEnd::Image) => {
+                let (skip_all, text_end) = open_links.pop().unwrap_or((true, range.start));
+                ranges.push(if skip_all { range.start } else { text_end }..range.end);
+                if let Some((_, outer_text_end)) = open_links.last_mut() {
+                    *outer_text_end = range.end;
+                }
+            }
+            _ => {
+                if let Some((_, text_end)) = open_links.last_mut() {
+                    *text_end = (*text_end).max(range.end);
+                }
+            }
+        }
+    }
+    ranges.extend(
+        parser
+            .reference_definitions()
+            .iter()
+            .map(|(_, definition)| definition.span.clone()),
+    );
+    ranges.sort_by_key(|range| range.start);
+    ranges
+}
+
+// The source and the end of the `$$…$$` formula starting at `start`.
+fn find_display(markdown: &str, start: usize) -> Option<(&str, usize)> {
+    let content_start = start + 2;
+    let len = markdown[content_start..].find("$$")?;
+    let source = &markdown[content_start..content_start + len];
+    if source.trim().is_empty() {
+        return None;
+    }
+    Some((source, content_start + len + 2))
+}
+
+// Like Pandoc, the opening `$` must be followed by a non-space and the
+// closing `$` must follow a non-space and not be followed by a digit, so
+// that "$5 and $10" stays text. The first unescaped `$` closes the formula
+// or it isn't one. Inline formulas don't span paragraphs.
+fn find_inline(markdown: &str, start: usize) -> Option<(&str, usize)> {
+    let content_start = start + 1;
+    let bytes = markdown.as_bytes();
+    if bytes
+        .get(content_start)
+        .map_or(true, u8::is_ascii_whitespace)
+    {
+        return None;
+    }
+
+    let mut i = content_start;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'\n'
+                if markdown[i + 1..]
+                    .trim_start_matches([' ', '\t'])
+                    .starts_with('\n') =>
+            {
+                return None
+            }
+            b'$' => {
+                let closes = !bytes[i - 1].is_ascii_whitespace()
+                    && !bytes.get(i + 1).is_some_and(u8::is_ascii_digit);
+                return closes.then(|| (&markdown[content_start..i], i + 1));
+            }
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// Converts a LaTeX formula to a MathML `<math>` element.
+///
+/// Only this subset of LaTeX math is supported, anything else is an error
+/// that is shown in place of the formula:
+///
+/// - sub- and superscripts, with limits below and above large operators and
+///   functions like `\lim` in display math
+/// - `\frac` and its variants, `\binom`, `\sqrt` and `\sqrt[n]`
+/// - Greek letters, operators, relations and arrows, see [`symbol`]
+/// - function names like `\sin`, and `\operatorname`
+/// - accents like `\hat` and `\overline`, `\underline` and braces
+/// - `\left…\right` and the `\big` delimiters
+/// - `\text`, `\mathrm`, `\mathbf`, `\mathbb`, `\mathcal` and spacing
+/// - the `matrix`, `cases`, `aligned` and `gathered` environments and their
+///   variants
+///
+/// There are no macros, and formulas can be nested [`MAX_DEPTH`] levels deep.
+/// The LaTeX to MathML crates either run KaTeX in a JavaScript engine or
+/// don't recommend themselves for production use yet, and this subset
+/// covers what blog posts use.
+pub(crate) fn to_mathml(latex: &str, display: bool) -> Result<String, String> {
+    let mut parser = TexParser {
+        source: latex,
+        pos: 0,
+        display,
+        variant: None,
+        depth: 0,
+    };
+    let rows = parser.table(None)?;
+    let content = match rows.as_slice() {
+        [row] if row.len() == 1 => row[0].clone(),
+        _ => mtable(&rows, "center"),
+    };
+    let display = if display { r#" display="block""# } else { "" };
+    Ok(format!(
+        r#"<math xmlns="http://www.w3.org/1998/Math/MathML"{display}>{content}</math>"#
+    ))
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Char(char),
+    Command(String),
+}
+
+// Why a row ended.
+enum Stop {
+    Eof,
+    Brace,
+    Align,
+    NewRow,
+    Right,
+    End(String),
+}
+
+struct Node {
+    mathml: String,
+    // Takes its scripts as limits above and below in display math.
+    limits: bool,
+}
+
+impl Node {
+    fn new(mathml: String) -> Self {
+        Self {
+            mathml,
+            limits: false,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Variant {
+    Normal,
+    Bold,
+    DoubleStruck,
+    Script,
+}
+
+struct TexParser<'a> {
+    source: &'a str,
+    pos: usize,
+    display: bool,
+    variant: Option<Variant>,
+    // Groups, arguments and environments the parser is currently inside of.
+    depth: usize,
+}
+
+// Deep enough for any formula written by hand, shallow enough that parsing
+// can't overflow the stack of a blocking thread.
+const MAX_DEPTH: usize = 64;
+
+impl TexParser<'_> {
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.source[self.pos..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.source[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let c = self.source[self.pos..].chars().next()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn next_token(&mut self) -> Result<Option<Token>, String> {
+        self.skip_whitespace();
+        let Some(c) = self.next_char() else {
+            return Ok(None);
+        };
+        if c != '\\' {
+            return Ok(Some(Token::Char(c)));
+        }
+
+        let rest = &self.source[self.pos..];
+        let len = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        if len > 0 {
+            self.pos += len;
+            return Ok(Some(Token::Command(rest[..len].to_string())));
+        }
+        match self.next_char() {
+            Some(c) => Ok(Some(Token::Command(c.to_string()))),
+            None => Err("Unexpected \\ at the end".to_string()),
+        }
+    }
+
+    // Cells separated by `&` in rows separated by `\\`, up to `\end{env}`,
+    // or up to the end of the formula if `env` is `None`.
+    fn table(&mut self, env: Option<&str>) -> Result<Vec<Vec<String>>, String> {
+        let mut rows = vec![Vec::new()];
+        loop {
+            let (cell, stop) = self.row()?;
+            let last_row = rows.last_mut().expect("rows is never empty");
+            last_row.push(cell);
+            match stop {
+                Stop::Align => {}
+                Stop::NewRow => rows.push(Vec::new()),
+                Stop::Eof if env.is_none() => break,
+                Stop::End(name) if Some(name.as_str()) == env => break,
+                Stop::Eof => return Err(format!("Missing \\end{{{}}}", env.unwrap_or(""))),
+                Stop::End(name) => return Err(format!("Unexpected \\end{{{name}}}")),
+                Stop::Brace => return Err("Unexpected }".to_string()),
+                Stop::Right => return Err("\\right without \\left".to_string()),
+            }
+        }
+        // A `\\` after the last row doesn't start another one.
+        if rows.len() > 1
+            && rows
+                .last()
+                .is_some_and(|r| r.len() == 1 && r[0] == "<mrow></mrow>")
+        {
+            rows.pop();
+        }
+        Ok(rows)
+    }
+
+    // Every recursion of the parser goes through `row` or `atom`.
+    fn nested<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<T, String>,
+    ) -> Result<T, String> {
+        if self.depth >= MAX_DEPTH {
+            return Err("Formula nested too deeply".to_string());
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn row(&mut self) -> Result<(String, Stop), String> {
+        self.nested(Self::row_items)
+    }
+
+    fn row_items(&mut self) -> Result<(String, Stop), String> {
+        let mut items: Vec<Node> = Vec::new();
+        let stop = loop {
+            let Some(token) = self.next_token()? else {
+                break Stop::Eof;
+            };
+            match token {
+                Token::Char('}') => break Stop::Brace,
+                Token::Char('&') => break Stop::Align,
+                Token::Command(c) if c == "\\" || c == "cr" => break Stop::NewRow,
+                Token::Command(c) if c == "right" => break Stop::Right,
+                Token::Command(c) if c == "end" => break Stop::End(self.text_argument()?),
+                Token::Char(c @ ('^' | '_')) => {
+                    let base = items
+                        .pop()
+                        .unwrap_or_else(|| Node::new("<mrow></mrow>".to_string()));
+                    let scripts = self.scripts(base, c)?;
+                    items.push(scripts);
+                }
+                token => items.push(self.atom(token)?),
+            }
+        };
+        Ok((mrow(items.into_iter().map(|n| n.mathml).collect()), stop))
+    }
+
+    // A group in braces or a single atom, as the argument of a command.
+    fn argument(&mut self, command: &str) -> Result<String, String> {
+        match self.next_token()? {
+            None => Err(format!("Missing argument for {command}")),
+            Some(Token::Char('{')) => self.group(),
+            Some(Token::Char(c @ ('}' | '&' | '^' | '_'))) => {
+                Err(format!("Missing argument for {command} before {c}"))
+            }
+            Some(token) => Ok(self.atom(token)?.mathml),
+        }
+    }
+
+    // The rest of a group after its `{`.
+    fn group(&mut self) -> Result<String, String> {
+        match self.row()? {
+            (row, Stop::Brace) => Ok(row),
+            (_, Stop::Eof) => Err("Missing }".to_string()),
+            (_, Stop::Right) => Err("\\right without \\left".to_string()),
+            (_, Stop::End(name)) => Err(format!("Unexpected \\end{{{name}}}")),
+            (_, Stop::Align | Stop::NewRow) => Err("Unexpected & or \\\\".to_string()),
+        }
+    }
+
+    // The raw text of a `{…}` argument, e.g. of `\text` or `\begin`.
+    fn text_argument(&mut self) -> Result<String, String> {
+        if self.peek() != Some('{') {
+            return Err("Expected {".to_string());
+        }
+        self.next_char();
+        let rest = &self.source[self.pos..];
+        let mut depth = 0;
+        for (i, c) in rest.char_indices() {
+            match c {
+                '{' => depth += 1,
+                '}' if depth == 0 => {
+                    self.pos += i + 1;
+                    return Ok(rest[..i].to_string());
+                }
+                '}' => depth -= 1,
+                _ => {}
+            }
+        }
+        Err("Missing }".to_string())
+    }
+
+    fn scripts(&mut self, base: Node, first: char) -> Result<Node, String> {
+        let (mut sub, mut sup) = (None, None);
+        let mut script = first;
+        loop {
+            let slot = if script == '_' { &mut sub } else { &mut sup };
+            if slot.is_some() {
+                return Err(format!("Double {}", script_name(script)));
+            }
+            *slot = Some(self.argument(&script.to_string())?);
+
+            match self.peek() {
+                Some(c @ ('^' | '_')) => {
+                    self.next_char();
+                    script = c;
+                }
+                _ => break,
+            }
+        }
+
+        let limits = base.limits && self.display;
+        let base = base.mathml;
+        let mathml = match (sub, sup, limits) {
+            (Some(sub), Some(sup), true) => format!("<munderover>{base}{sub}{sup}</munderover>"),
+            (Some(sub), None, true) => format!("<munder>{base}{sub}</munder>"),
+            (None, Some(sup), true) => format!("<mover>{base}{sup}</mover>"),
+            (Some(sub), Some(sup), false) => format!("<msubsup>{base}{sub}{sup}</msubsup>"),
+            (Some(sub), None, false) => format!("<msub>{base}{sub}</msub>"),
+            (None, Some(sup), false) => format!("<msup>{base}{sup}</msup>"),
+            (None, None, _) => base,
+        };
+        Ok(Node::new(mathml))
+    }
+
+    fn atom(&mut self, token: Token) -> Result<Node, String> {
+        self.nested(|parser| parser.single_atom(token))
+    }
+
+    fn single_atom(&mut self, token: Token) -> Result<Node, String> {
+        let c = match token {
+            Token::Command(name) => return self.command(&name),
+            Token::Char(c) => c,
+        };
+        let mathml = match c {
+            '{' => self.group()?,
+            '0'..='9' | '.' => {
+                let start = self.pos - 1;
+                let rest = &self.source[start..];
+                let len = rest
+                    .find(|c: char| !c.is_ascii_digit() && c != '.')
+                    .unwrap_or(rest.len());
+                self.pos = start + len;
+                let number = &rest[..len];
+                if number == "." {
+                    mo(".")
+                } else {
+                    format!("<mn>{}</mn>", self.styled(number))
+                }
+            }
+            '\'' => mo("\u{2032}"),
+            '-' => mo("\u{2212}"),
+            '*' => mo("\u{2217}"),
+            '~' => mspace("0.333em"),
+            '#' | '%' => return Err(format!("Unexpected {c}")),
+            c if c.is_alphabetic() => self.identifier(c),
+            c => mo(&c.to_string()),
+        };
+        Ok(Node::new(mathml))
+    }
+
+    fn identifier(&self, c: char) -> String {
+        match self.variant {
+            Some(Variant::Normal) => format!(
+                r#"<mi mathvariant="normal">{}</mi>"#,
+                escape_html(&c.to_string())
+            ),
+            _ => format!("<mi>{}</mi>", self.styled(&c.to_string())),
+        }
+    }
+
+    // The letters and digits in the current font, as Unicode mathematical
+    // alphanumerics since MathML Core only has `mathvariant="normal"`.
+    fn styled(&self, text: &str) -> String {
+        let variant = self.variant;
+        let styled = text
+            .chars()
+            .map(|c| variant.and_then(|v| styled_char(c, v)).unwrap_or(c))
+            .collect::<String>();
+        escape_html(&styled)
+    }
+
+    fn command(&mut self, name: &str) -> Result<Node, String> {
+        if let Some((symbol, kind)) = symbol(name) {
+            let mathml = match kind {
+                Kind::Identifier => format!("<mi>{symbol}</mi>"),
+                Kind::Upright => format!(r#"<mi mathvariant="normal">{symbol}</mi>"#),
+                Kind::Operator | Kind::LargeOperator => mo(symbol),
+            };
+            return Ok(Node {
+                mathml,
+                limits: matches!(kind, Kind::LargeOperator),
+            });
+        }
+        // Multi-letter identifiers are upright.
+        if FUNCTIONS.contains(&name) || LIMIT_FUNCTIONS.contains(&name) {
+            return Ok(Node {
+                mathml: format!("<mi>{name}</mi>"),
+                limits: LIMIT_FUNCTIONS.contains(&name),
+            });
+        }
+
+        let mathml = match name {
+            "frac" | "dfrac" | "tfrac" | "cfrac" => {
+                let numerator = self.argument(name)?;
+                let denominator = self.argument(name)?;
+                format!("<mfrac>{numerator}{denominator}</mfrac>")
+            }
+            "binom" => {
+                let n = self.argument(name)?;
+                let k = self.argument(name)?;
+                format!(
+                    r#"<mrow><mo>(</mo><mfrac linethickness="0">{n}{k}</mfrac><mo>)</mo></mrow>"#
+                )
+            }
+            "sqrt" => {
+                if self.peek() == Some('[') {
+                    self.next_char();
+                    let index = self.optional_argument()?;
+                    let radicand = self.argument(name)?;
+                    format!("<mroot>{radicand}{index}</mroot>")
+                } else {
+                    format!("<msqrt>{}</msqrt>", self.argument(name)?)
+                }
+            }
+            "text" | "textrm" | "textit" | "textbf" | "mbox" => {
+                format!("<mtext>{}</mtext>", escape_html(&self.text_argument()?))
+            }
+            "operatorname" => format!("<mi>{}</mi>", escape_html(&self.text_argument()?)),
+            "mathrm" | "mathbf" | "mathbb" | "mathcal" | "mathit" | "boldsymbol" => {
+                let outer = self.variant;
+                self.variant = match name {
+                    "mathrm" => Some(Variant::Normal),
+                    "mathbf" | "boldsymbol" => Some(Variant::Bold),
+                    "mathbb" => Some(Variant::DoubleStruck),
+                    "mathcal" => Some(Variant::Script),
+                    _ => None,
+                };
+                let argument = self.argument(name);
+                self.variant = outer;
+                argument?
+            }
+            "hat" | "widehat" | "bar" | "overline" | "vec" | "tilde" | "widetilde" | "dot"
+            | "ddot" | "overbrace" => {
+                let accent = match name {
+                    "hat" | "widehat" => "^",
+                    "bar" | "overline" => "\u{AF}",
+                    "vec" => "\u{2192}",
+                    "tilde" | "widetilde" => "~",
+                    "dot" => "\u{2D9}",
+                    "ddot" => "\u{A8}",
+                    _ => "\u{23DE}",
+                };
+                let base = self.argument(name)?;
+                format!(r#"<mover accent="true">{base}{}</mover>"#, mo(accent))
+            }
+            "underline" | "underbrace" => {
+                let accent = if name == "underline" { "_" } else { "\u{23DF}" };
+                let base = self.argument(name)?;
+                format!(
+                    r#"<munder accentunder="true">{base}{}</munder>"#,
+                    mo(accent)
+                )
+            }
+            "left" => {
+                let open = self.delimiter(name)?;
+                let (content, stop) = self.row()?;
+                if !matches!(stop, Stop::Right) {
+                    return Err("\\left without \\right".to_string());
+                }
+                let close = self.delimiter("\\right")?;
+                format!("<mrow>{}{content}{}</mrow>", fence(&open), fence(&close))
+            }
+            "big" | "Big" | "bigg" | "Bigg" | "bigl" | "bigr" | "Bigl" | "Bigr" | "biggl"
+            | "biggr" | "Biggl" | "Biggr" => mo(&self.delimiter(name)?),
+            "begin" => {
+                let env = self.text_argument()?;
+                self.environment(&env)?
+            }
+            "," => mspace("0.1667em"),
+            ":" | ">" => mspace("0.2222em"),
+            ";" => mspace("0.2778em"),
+            " " => mspace("0.333em"),
+            "quad" => mspace("1em"),
+            "qquad" => mspace("2em"),
+            "!" => mspace("-0.1667em"),
+            "{" | "}" | "|" | "$" | "%" | "#" | "&" | "_" => {
+                mo(if name == "|" { "\u{2016}" } else { name })
+            }
+            _ => return Err(format!("Unknown command \\{name}")),
+        };
+        Ok(Node::new(mathml))
+    }
+
+    // The rest of a `[…]` argument after its `[`.
+    fn optional_argument(&mut self) -> Result<String, String> {
+        let rest = &self.source[self.pos..];
+        let len = rest.find(']').ok_or("Missing ]")?;
+        self.pos += len + 1;
+        let mut parser = TexParser {
+            source: &rest[..len],
+            pos: 0,
+            display: self.display,
+            variant: self.variant,
+            depth: self.depth,
+        };
+        match parser.row()? {
+            (row, Stop::Eof) => Ok(row),
+            _ => Err("Unexpected token in [ ]".to_string()),
+        }
+    }
+
+    fn delimiter(&mut self, command: &str) -> Result<String, String> {
+        let delimiter = match self.next_token()? {
+            Some(Token::Char('.')) => "",
+            Some(Token::Char('<')) => "\u{27E8}",
+            Some(Token::Char('>')) => "\u{27E9}",
+            Some(Token::Char(c @ ('(' | ')' | '[' | ']' | '|' | '/'))) => return Ok(c.to_string()),
+            Some(Token::Command(name)) => match name.as_str() {
+                "{" | "lbrace" => "{",
+                "}" | "rbrace" => "}",
+                "|" | "Vert" | "lVert" | "rVert" => "\u{2016}",
+                "vert" | "lvert" | "rvert" => "|",
+                "langle" => "\u{27E8}",
+                "rangle" => "\u{27E9}",
+                "lfloor" => "\u{230A}",
+                "rfloor" => "\u{230B}",
+                "lceil" => "\u{2308}",
+                "rceil" => "\u{2309}",
+                _ => return Err(format!("Invalid delimiter \\{name} after {command}")),
+            },
+            _ => return Err(format!("Missing delimiter after {command}")),
+        };
+        Ok(delimiter.to_string())
+    }
+
+    fn environment(&mut self, env: &str) -> Result<String, String> {
+        let (open, close, align) = match env {
+            "matrix" | "smallmatrix" => ("", "", "center"),
+            "pmatrix" => ("(", ")", "center"),
+            "bmatrix" => ("[", "]", "center"),
+            "Bmatrix" => ("{", "}", "center"),
+            "vmatrix" => ("|", "|", "center"),
+            "Vmatrix" => ("\u{2016}", "\u{2016}", "center"),
+            "cases" => ("{", "", "left"),
+            "aligned" | "align" | "align*" | "split" => ("", "", "right left"),
+            "gathered" | "gather" | "gather*" => ("", "", "center"),
+            _ => return Err(format!("Unknown environment {env}")),
+        };
+        let rows = self.table(Some(env))?;
+        let table = mtable(&rows, align);
+        if open.is_empty() && close.is_empty() {
+            return Ok(table);
+        }
+        Ok(format!(
+            "<mrow>{}{table}{}</mrow>",
+            fence(open),
+            fence(close)
+        ))
+    }
+}
+
+fn script_name(script: char) -> &'static str {
+    if script == '_' {
+        "subscript"
+    } else {
+        "superscript"
+    }
+}
+
+fn mrow(items: Vec<String>) -> String {
+    match <[String; 1]>::try_from(items) {
+        Ok([item]) => item,
+        Err(items) => format!("<mrow>{}</mrow>", items.concat()),
+    }
+}
+
+fn mtable(rows: &[Vec<String>], align: &str) -> String {
+    let rows = rows
+        .iter()
+        .map(|row| {
+            let cells = row
+                .iter()
+                .map(|cell| format!("<mtd>{cell}</mtd>"))
+                .collect::<String>();
+            format!("<mtr>{cells}</mtr>")
+        })
+        .collect::<String>();
+    format!(r#"<mtable columnalign="{align}">{rows}</mtable>"#)
+}
+
+fn mo(operator: &str) -> String {
+    format!("<mo>{}</mo>", escape_html(operator))
+}
+
+fn mspace(width: &str) -> String {
+    format!(r#"<mspace width="{width}"/>"#)
+}
+
+fn fence(delimiter: &str) -> String {
+    if delimiter.is_empty() {
+        return String::new();
+    }
+    format!(r#"<mo fence="true">{}</mo>"#, escape_html(delimiter))
+}
+
+fn styled_char(c: char, variant: Variant) -> Option<char> {
+    let (upper, lower, digit, exceptions): (u32, u32, Option<u32>, &[(char, char)]) = match variant
+    {
+        Variant::Normal => return None,
+        Variant::Bold => (0x1D400, 0x1D41A, Some(0x1D7CE), &[]),
+        Variant::DoubleStruck => (
+            0x1D538,
+            0x1D552,
+            Some(0x1D7D8),
+            &[
+                ('C', 'ℂ'),
+                ('H', 'ℍ'),
+                ('N', 'ℕ'),
+                ('P', 'ℙ'),
+                ('Q', 'ℚ'),
+                ('R', 'ℝ'),
+                ('Z', 'ℤ'),
+            ],
+        ),
+        Variant::Script => (
+            0x1D49C,
+            0x1D4B6,
+            None,
+            &[
+                ('B', 'ℬ'),
+                ('E', 'ℰ'),
+                ('F', 'ℱ'),
+                ('H', 'ℋ'),
+                ('I', 'ℐ'),
+                ('L', 'ℒ'),
+                ('M', 'ℳ'),
+                ('R', 'ℛ'),
+                ('e', 'ℯ'),
+                ('g', 'ℊ'),
+                ('o', 'ℴ'),
+            ],
+        ),
+    };
+    if let Some((_, styled)) = exceptions.iter().find(|(plain, _)| *plain == c) {
+        return Some(*styled);
+    }
+    let code = match c {
+        'A'..='Z' => upper + (c as u32 - 'A' as u32),
+        'a'..='z' => lower + (c as u32 - 'a' as u32),
+        '0'..='9' => digit? + (c as u32 - '0' as u32),
+        _ => return None,
+    };
+    char::from_u32(code)
+}
+
+enum Kind {
+    Identifier,
+    // Upright, like capital Greek letters in TeX.
+    Upright,
+    Operator,
+    LargeOperator,
+}
+
+const FUNCTIONS: &[&str] = &[
+    "sin", "cos", "tan", "cot", "sec", "csc", "arcsin", "arccos", "arctan", "sinh", "cosh", "tanh",
+    "log", "ln", "lg", "exp", "deg", "dim", "ker", "arg", "hom", "mod",
+];
+
+// Functions that take their subscript below in display math, like `\lim`.
+const LIMIT_FUNCTIONS: &[&str] = &[
+    "lim", "liminf", "limsup", "max", "min", "sup", "inf", "det", "gcd", "Pr",
+];
+
+fn symbol(name: &str) -> Option<(&'static str, Kind)> {
+    use Kind::*;
+    let symbol = match name {
+        "alpha" => ("α", Identifier),
+        "beta" => ("β", Identifier),
+        "gamma" => ("γ", Identifier),
+        "delta" => ("δ", Identifier),
+        "epsilon" => ("ϵ", Identifier),
+        "varepsilon" => ("ε", Identifier),
+        "zeta" => ("ζ", Identifier),
+        "eta" => ("η", Identifier),
+        "theta" => ("θ", Identifier),
+        "vartheta" => ("ϑ", Identifier),
+        "iota" => ("ι", Identifier),
+        "kappa" => ("κ", Identifier),
+        "lambda" => ("λ", Identifier),
+        "mu" => ("μ", Identifier),
+        "nu" => ("ν", Identifier),
+        "xi" => ("ξ", Identifier),
+        "pi" => ("π", Identifier),
+        "varpi" => ("ϖ", Identifier),
+        "rho" => ("ρ", Identifier),
+        "varrho" => ("ϱ", Identifier),
+        "sigma" => ("σ", Identifier),
+        "varsigma" => ("ς", Identifier),
+        "tau" => ("τ", Identifier),
+        "upsilon" => ("υ", Identifier),
+        "phi" => ("ϕ", Identifier),
+        "varphi" => ("φ", Identifier),
+        "chi" => ("χ", Identifier),
+        "psi" => ("ψ", Identifier),
+        "omega" => ("ω", Identifier),
+        "Gamma" => ("Γ", Upright),
+        "Delta" => ("Δ", Upright),
+        "Theta" => ("Θ", Upright),
+        "Lambda" => ("Λ", Upright),
+        "Xi" => ("Ξ", Upright),
+        "Pi" => ("Π", Upright),
+        "Sigma" => ("Σ", Upright),
+        "Upsilon" => ("Υ", Upright),
+        "Phi" => ("Φ", Upright),
+        "Psi" => ("Ψ", Upright),
+        "Omega" => ("Ω", Upright),
+        "partial" => ("∂", Identifier),
+        "infty" => ("∞", Identifier),
+        "emptyset" | "varnothing" => ("∅", Identifier),
+        "aleph" => ("ℵ", Identifier),
+        "hbar" => ("ℏ", Identifier),
+        "ell" => ("ℓ", Identifier),
+        "Re" => ("ℜ", Identifier),
+        "Im" => ("ℑ", Identifier),
+        "nabla" => ("∇", Operator),
+        "pm" => ("±", Operator),
+        "mp" => ("∓", Operator),
+        "times" => ("×", Operator),
+        "div" => ("÷", Operator),
+        "cdot" => ("⋅", Operator),
+        "ast" => ("∗", Operator),
+        "star" => ("⋆", Operator),
+        "circ" => ("∘", Operator),
+        "bullet" => ("∙", Operator),
+        "oplus" => ("⊕", Operator),
+        "ominus" => ("⊖", Operator),
+        "otimes" => ("⊗", Operator),
+        "odot" => ("⊙", Operator),
+        "cup" => ("∪", Operator),
+        "cap" => ("∩", Operator),
+        "setminus" => ("∖", Operator),
+        "wedge" | "land" => ("∧", Operator),
+        "vee" | "lor" => ("∨", Operator),
+        "neg" | "lnot" => ("¬", Operator),
+        "leq" | "le" => ("≤", Operator),
+        "geq" | "ge" => ("≥", Operator),
+        "neq" | "ne" => ("≠", Operator),
+        "approx" => ("≈", Operator),
+        "equiv" => ("≡", Operator),
+        "sim" => ("∼", Operator),
+        "simeq" => ("≃", Operator),
+        "cong" => ("≅", Operator),
+        "propto" => ("∝", Operator),
+        "ll" => ("≪", Operator),
+        "gg" => ("≫", Operator),
+        "prec" => ("≺", Operator),
+        "succ" => ("≻", Operator),
+        "subset" => ("⊂", Operator),
+        "supset" => ("⊃", Operator),
+        "subseteq" => ("⊆", Operator),
+        "supseteq" => ("⊇", Operator),
+        "in" => ("∈", Operator),
+        "notin" => ("∉", Operator),
+        "ni" => ("∋", Operator),
+        "perp" => ("⊥", Operator),
+        "parallel" => ("∥", Operator),
+        "mid" => ("∣", Operator),
+        "to" | "rightarrow" => ("→", Operator),
+        "gets" | "leftarrow" => ("←", Operator),
+        "leftrightarrow" => ("↔", Operator),
+        "Rightarrow" => ("⇒", Operator),
+        "Leftarrow" => ("⇐", Operator),
+        "Leftrightarrow" => ("⇔", Operator),
+        "implies" | "Longrightarrow" => ("⟹", Operator),
+        "iff" | "Longleftrightarrow" => ("⟺", Operator),
+        "longrightarrow" => ("⟶", Operator),
+        "longleftarrow" => ("⟵", Operator),
+        "mapsto" => ("↦", Operator),
+        "uparrow" => ("↑", Operator),
+        "downarrow" => ("↓", Operator),
+        "forall" => ("∀", Operator),
+        "exists" => ("∃", Operator),
+        "nexists" => ("∄", Operator),
+        "ldots" | "dots" => ("…", Operator),
+        "cdots" => ("⋯", Operator),
+        "vdots" => ("⋮", Operator),
+        "ddots" => ("⋱", Operator),
+        "langle" => ("⟨", Operator),
+        "rangle" => ("⟩", Operator),
+        "lfloor" => ("⌊", Operator),
+        "rfloor" => ("⌋", Operator),
+        "lceil" => ("⌈", Operator),
+        "rceil" => ("⌉", Operator),
+        "vert" | "lvert" | "rvert" => ("|", Operator),
+        "Vert" | "lVert" | "rVert" => ("‖", Operator),
+        "angle" => ("∠", Operator),
+        "triangle" => ("△", Operator),
+        "prime" => ("′", Operator),
+        "colon" => (":", Operator),
+        "sum" => ("∑", LargeOperator),
+        "prod" => ("∏", LargeOperator),
+        "coprod" => ("∐", LargeOperator),
+        "bigcup" => ("⋃", LargeOperator),
+        "bigcap" => ("⋂", LargeOperator),
+        "bigoplus" => ("⨁", LargeOperator),
+        "bigotimes" => ("⨂", LargeOperator),
+        "bigvee" => ("⋁", LargeOperator),
+        "bigwedge" => ("⋀", LargeOperator),
+        // Integrals keep their limits as scripts, like in TeX.
+        "int" => ("∫", Operator),
+        "iint" => ("∬", Operator),
+        "iiint" => ("∭", Operator),
+        "oint" => ("∮", Operator),
+        _ => return None,
+    };
+    Some(symbol)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inner(latex: &str) -> String {
+        let mathml = to_mathml(latex, false).unwrap();
+        let start = mathml.find('>').unwrap() + 1;
+        mathml[start..mathml.len() - "</math>".len()].to_string()
+    }
+
+    #[test]
+    fn converts_scripts_fractions_and_roots() {
+        assert_eq!(
+            inner("x^2 + y_i"),
+            "<mrow><msup><mi>x</mi><mn>2</mn></msup><mo>+</mo><msub><mi>y</mi><mi>i</mi></msub></mrow>"
+        );
+        assert_eq!(
+            inner(r"\frac{a}{\sqrt[3]{b}}"),
+            "<mfrac><mi>a</mi><mroot><mi>b</mi><mn>3</mn></mroot></mfrac>"
+        );
+        assert_eq!(
+            inner(r"\alpha \leq \Omega"),
+            r#"<mrow><mi>α</mi><mo>≤</mo><mi mathvariant="normal">Ω</mi></mrow>"#
+        );
+    }
+
+    #[test]
+    fn large_operators_take_limits_in_display_math() {
+        let display = to_mathml(r"\sum_{i=1}^n i", true).unwrap();
+        assert!(display
+            .starts_with(r#"<math xmlns="http://www.w3.org/1998/Math/MathML" display="block">"#));
+        assert!(display.contains("<munderover><mo>∑</mo>"));
+
+        assert!(inner(r"\sum_{i=1}^n i").contains("<msubsup><mo>∑</mo>"));
+    }
+
+    #[test]
+    fn converts_fences_text_and_environments() {
+        assert_eq!(
+            inner(r"\left( \text{if } x \right]"),
+            r#"<mrow><mo fence="true">(</mo><mrow><mtext>if </mtext><mi>x</mi></mrow><mo fence="true">]</mo></mrow>"#
+        );
+        assert_eq!(
+            inner(r"\begin{pmatrix} 1 & 0 \\ 0 & 1 \\ \end{pmatrix}"),
+            r#"<mrow><mo fence="true">(</mo><mtable columnalign="center"><mtr><mtd><mn>1</mn></mtd><mtd><mn>0</mn></mtd></mtr><mtr><mtd><mn>0</mn></mtd><mtd><mn>1</mn></mtd></mtr></mtable><mo fence="true">)</mo></mrow>"#
+        );
+        assert_eq!(inner(r"\mathbb{R}^n"), "<msup><mi>ℝ</mi><mi>n</mi></msup>");
+    }
+
+    #[test]
+    fn invalid_latex_is_an_error() {
+        assert_eq!(
+            to_mathml(r"\foo", false).unwrap_err(),
+            r"Unknown command \foo"
+        );
+        assert_eq!(to_mathml(r"\frac{a", false).unwrap_err(), "Missing }");
+        assert_eq!(
+            to_mathml("x^", false).unwrap_err(),
+            "Missing argument for ^"
+        );
+        assert_eq!(to_mathml("x^1^2", false).unwrap_err(), "Double superscript");
+        assert!(to_mathml(r"\left( x", false).is_err());
+        assert!(to_mathml(r"\begin{matrix} x", false).is_err());
+    }
+
+    #[test]
+    fn deeply_nested_formulas_are_an_error() {
+        let braces = format!("{}x{}", "{".repeat(100_000), "}".repeat(100_000));
+        assert_eq!(
+            to_mathml(&braces, false).unwrap_err(),
+            "Formula nested too deeply"
+        );
+        let accents = format!("{}x", r"\hat".repeat(100_000));
+        assert_eq!(
+            to_mathml(&accents, false).unwrap_err(),
+            "Formula nested too deeply"
+        );
+
+        let nested = format!("{}x{}", "{".repeat(20), "}".repeat(20));
+        assert!(to_mathml(&nested, false).is_ok());
+    }
+
+    #[test]
+    fn extracts_formulas_outside_of_code() {
+        let markdown = "Costs $5 and $10, $a_1$ and `$b$`\n\n$$\nx\n$$\n\n```\n$c$\n```\n";
+        let (extracted, formulas) = Formulas::extract(markdown, Options::empty());
+
+        assert_eq!(
+            extracted,
+            "Costs $5 and $10, \u{E000}0\u{E001} and `$b$`\n\n\u{E000}1\u{E001}\n\n```\n$c$\n```\n"
+        );
+        assert_eq!(formulas.restore("\u{E000}0\u{E001}!"), "a_1!");
+        assert_eq!(formulas.formulas[1].source, "x");
+        assert!(formulas.formulas[1].display);
+    }
+
+    #[test]
+    fn formulas_in_link_destinations_are_not_extracted() {
+        let markdown = "[$a$](/$b$ \"$c$\") ![d](/$e$.png) <https://x.org/$f$> [g][r]\n\n\
+                        [r]: /$h$";
+        let (extracted, formulas) = Formulas::extract(markdown, Options::empty());
+
+        assert_eq!(
+            extracted,
+            "[\u{E000}0\u{E001}](/$b$ \"$c$\") ![d](/$e$.png) \
+             <https://x.org/$f$> [g][r]\n\n[r]: /$h$"
+        );
+        assert_eq!(formulas.restore("\u{E000}0\u{E001}"), "a");
+    }
+
+    #[test]
+    fn formulas_in_image_alt_text_are_not_extracted() {
+        let markdown = "![$a$ and $b$](/x.png) [![$c$](/y.png) $d$](/z)";
+        let (extracted, formulas) = Formulas::extract(markdown, Options::empty());
+
+        assert_eq!(
+            extracted,
+            "![$a$ and $b$](/x.png) [![$c$](/y.png) \u{E000}0\u{E001}](/z)"
+        );
+        assert_eq!(formulas.restore("\u{E000}0\u{E001}"), "d");
+    }
+
+    #[test]
+    fn formulas_in_heading_attribute_blocks_are_not_extracted() {
+        let markdown = "# $x$ {#$y$}\n\nSetext $z$ {.$w$}\n===";
+
+        let (extracted, _) = Formulas::extract(markdown, Options::ENABLE_HEADING_ATTRIBUTES);
+        assert_eq!(
+            extracted,
+            "# \u{E000}0\u{E001} {#$y$}\n\nSetext \u{E000}1\u{E001} {.$w$}\n==="
+        );
+
+        let (extracted, _) = Formulas::extract(markdown, Options::empty());
+        assert_eq!(
+            extracted,
+            "# \u{E000}0\u{E001} {#\u{E000}1\u{E001}}\n\n\
+             Setext \u{E000}2\u{E001} {.\u{E000}3\u{E001}}\n==="
+        );
+    }
+
+    #[test]
+    fn escaped_dollars_are_not_formulas() {
+        let (extracted, formulas) = Formulas::extract(r"\$x$ and \$y$", Options::empty());
+        assert_eq!(extracted, r"\$x$ and \$y$");
+        assert!(formulas.formulas.is_empty());
+    }
+}
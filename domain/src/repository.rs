@@ -1,13 +1,75 @@
 use crate::blog::BlogPost;
-use crate::NewBlogPostData;
+use crate::{
+    ArchiveMonth, NewBlogPostData, PostQuery, PostRevision, PostStatusFilter, PostSummary,
+    RenderedPost, SearchResult, Series, Tag,
+};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use secrecy::Secret;
 
 #[async_trait]
 pub trait Repository: Send + Sync {
     async fn update_admin_password(&self, hashed_password: &str) -> anyhow::Result<()>;
     async fn load_admin_password(&self) -> anyhow::Result<Secret<String>>;
-    async fn store_blog_post(&self, new_post: &NewBlogPostData, html: &str) -> anyhow::Result<()>;
+    async fn store_blog_post(
+        &self,
+        new_post: &NewBlogPostData,
+        rendered: &RenderedPost,
+    ) -> anyhow::Result<()>;
+    async fn update_blog_post(
+        &self,
+        id: i64,
+        post: &NewBlogPostData,
+        rendered: &RenderedPost,
+    ) -> anyhow::Result<()>;
+    /// Replaces the rendered HTML without touching anything else.
+    async fn update_post_html(&self, id: i64, rendered: &RenderedPost) -> anyhow::Result<()>;
+    async fn update_post_published(&self, id: i64, publish: bool) -> anyhow::Result<()>;
+    async fn update_post_pinned(&self, id: i64, pinned: bool) -> anyhow::Result<()>;
+    async fn update_post_trashed(&self, id: i64, trashed: bool) -> anyhow::Result<()>;
+    async fn delete_post(&self, id: i64) -> anyhow::Result<()>;
     async fn load_all_posts(&self) -> anyhow::Result<Vec<BlogPost>>;
+    /// Posts with the status `filter` at `now`, or all posts that aren't in the
+    /// trash without a filter. Drafts first, then the newest publish date first.
+    async fn list_posts(
+        &self,
+        now: DateTime<Utc>,
+        filter: Option<PostStatusFilter>,
+    ) -> anyhow::Result<Vec<BlogPost>>;
+    /// Posts stored before excerpts and word counts were, which don't have them.
+    async fn load_unrendered_posts(&self) -> anyhow::Result<Vec<BlogPost>>;
+    /// Posts with `[TOC]` somewhere in their markdown but no table of contents
+    /// stored. The marker may also just be in code.
+    async fn load_posts_without_toc(&self) -> anyhow::Result<Vec<BlogPost>>;
     async fn load_post_by_url_id(&self, url_id: &str) -> anyhow::Result<Option<BlogPost>>;
+    /// Posts that are published at `now` and match `query`.
+    async fn list_published_posts(
+        &self,
+        now: DateTime<Utc>,
+        query: &PostQuery,
+    ) -> anyhow::Result<Vec<PostSummary>>;
+    /// The posts with the given ids, in no particular order.
+    async fn load_posts_by_ids(&self, ids: &[i64]) -> anyhow::Result<Vec<BlogPost>>;
+    /// Number of posts published at `now` per month, newest month first.
+    async fn count_published_posts_by_month(
+        &self,
+        now: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<ArchiveMonth>>;
+    /// Number of posts published at `now` per tag, sorted by tag name.
+    async fn count_published_posts_by_tag(&self, now: DateTime<Utc>) -> anyhow::Result<Vec<Tag>>;
+    /// At most `limit` posts that are published at `now` and match all words
+    /// of `query`, best matches first.
+    async fn search_posts(
+        &self,
+        now: DateTime<Utc>,
+        query: &str,
+        limit: u32,
+    ) -> anyhow::Result<Vec<SearchResult>>;
+    async fn load_series(&self, url_id: &str) -> anyhow::Result<Option<Series>>;
+    async fn load_post_revisions(&self, post_id: i64) -> anyhow::Result<Vec<PostRevision>>;
+    async fn load_post_revision(
+        &self,
+        post_id: i64,
+        revision_id: i64,
+    ) -> anyhow::Result<Option<PostRevision>>;
 }
@@ -0,0 +1,71 @@
+use chrono::{DateTime, Utc};
+use similar::{ChangeTag, TextDiff};
+
+#[derive(Debug)]
+pub struct PostRevision {
+    pub id: i64,
+    pub post_id: i64,
+    pub title: String,
+    pub markdown: String,
+    /// When this version of the post was replaced by an edit.
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DiffLine {
+    Unchanged(String),
+    Added(String),
+    Removed(String),
+}
+
+#[derive(Debug)]
+pub struct RevisionDiff {
+    pub revision: PostRevision,
+    pub current_title: String,
+    pub lines: Vec<DiffLine>,
+}
+
+pub(crate) fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    TextDiff::from_lines(old, new)
+        .iter_all_changes()
+        .map(|change| {
+            let line = change.value().trim_end_matches(['\r', '\n']).to_string();
+            match change.tag() {
+                ChangeTag::Equal => DiffLine::Unchanged(line),
+                ChangeTag::Insert => DiffLine::Added(line),
+                ChangeTag::Delete => DiffLine::Removed(line),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_text_has_no_changes() {
+        let lines = diff_lines("a\nb\n", "a\nb\n");
+        assert_eq!(
+            lines,
+            vec![
+                DiffLine::Unchanged("a".to_string()),
+                DiffLine::Unchanged("b".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn changed_line_is_removed_and_added() {
+        let lines = diff_lines("a\nb\nc", "a\nx\nc");
+        assert_eq!(
+            lines,
+            vec![
+                DiffLine::Unchanged("a".to_string()),
+                DiffLine::Removed("b".to_string()),
+                DiffLine::Added("x".to_string()),
+                DiffLine::Unchanged("c".to_string()),
+            ]
+        );
+    }
+}
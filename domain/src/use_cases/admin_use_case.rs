@@ -1,19 +1,34 @@
-use crate::{markdown, util, BlogPost, DomainError, NewBlogPostData, Repository};
+use crate::revision::diff_lines;
+use crate::{
+    front_matter, markdown, util, BlogPost, DomainError, MarkdownSettings, NewBlogPostData,
+    PostRevision, PostStatusFilter, RenderedPost, Repository, RevisionDiff, SyntaxHighlighting,
+};
 use anyhow::{anyhow, Context};
 use argon2::{Argon2, PasswordHash, PasswordVerifier};
+use chrono::Utc;
 use secrecy::{ExposeSecret, Secret};
+use std::sync::Arc;
+use syntect::parsing::SyntaxSet;
 
 pub struct AdminUseCase {
     repository: Box<dyn Repository>,
+    markdown_settings: MarkdownSettings,
+    highlighting: Arc<SyntaxHighlighting>,
 }
 
 impl AdminUseCase {
-    pub fn new<R>(repository: R) -> Self
+    pub fn new<R>(
+        repository: R,
+        markdown_settings: MarkdownSettings,
+        highlighting: Arc<SyntaxHighlighting>,
+    ) -> Self
     where
         R: Repository + 'static,
     {
         Self {
             repository: Box::new(repository),
+            markdown_settings,
+            highlighting,
         }
     }
 
@@ -24,19 +39,325 @@ impl AdminUseCase {
         Ok(posts)
     }
 
+    /// Drafts first, then the newest publish date first. Without a filter, all
+    /// posts that aren't in the trash are returned.
+    #[tracing::instrument(name = "Retrieving blog posts by status", skip(self))]
+    pub async fn get_posts(
+        &self,
+        filter: Option<PostStatusFilter>,
+    ) -> Result<Vec<BlogPost>, DomainError> {
+        let posts = self.repository.list_posts(Utc::now(), filter).await?;
+        Ok(posts)
+    }
+
+    #[tracing::instrument(name = "Retrieving all trashed blog posts", skip(self))]
+    pub async fn get_trashed_posts(&self) -> Result<Vec<BlogPost>, DomainError> {
+        self.get_posts(Some(PostStatusFilter::Trashed)).await
+    }
+
+    #[tracing::instrument(name = "Find blog post by url id", skip(self))]
+    pub async fn get_post_by_url_id(&self, url_id: &str) -> Result<Option<BlogPost>, DomainError> {
+        let post = self.repository.load_post_by_url_id(url_id).await?;
+        Ok(post)
+    }
+
+    /// Returns the url id of the stored post, which may come from the front
+    /// matter of the markdown.
     #[tracing::instrument(name = "Storing a new blog post", skip(self, new_blog))]
-    pub async fn store_blog_post(&self, new_blog: &NewBlogPostData) -> Result<(), DomainError> {
-        let html = self.generate_html(new_blog.markdown.clone()).await?;
-        self.repository.store_blog_post(new_blog, &html).await?;
+    pub async fn store_blog_post(&self, new_blog: &NewBlogPostData) -> Result<String, DomainError> {
+        let mut new_blog = new_blog.clone();
+        new_blog.apply_front_matter()?;
+        new_blog.validate_series()?;
+        self.ensure_url_id_available(&new_blog.url_id, None).await?;
+        let rendered = self
+            .render_post(
+                &new_blog.markdown,
+                new_blog.excerpt_markdown(&self.markdown_settings),
+            )
+            .await?;
+        self.repository
+            .store_blog_post(&new_blog, &rendered)
+            .await?;
+        Ok(new_blog.url_id)
+    }
+
+    /// Returns the url id of the updated post, which may come from the front
+    /// matter of the markdown.
+    #[tracing::instrument(name = "Updating an existing blog post", skip(self, blog))]
+    pub async fn update_blog_post(
+        &self,
+        url_id: &str,
+        blog: &NewBlogPostData,
+    ) -> Result<String, DomainError> {
+        let existing = self.get_existing_post(url_id).await?;
+        let mut blog = blog.clone();
+        blog.apply_front_matter()?;
+        blog.validate_series()?;
+        self.ensure_url_id_available(&blog.url_id, Some(existing.id))
+            .await?;
+        let rendered = self
+            .render_post(
+                &blog.markdown,
+                blog.excerpt_markdown(&self.markdown_settings),
+            )
+            .await?;
+        self.repository
+            .update_blog_post(existing.id, &blog, &rendered)
+            .await?;
+        Ok(blog.url_id)
+    }
+
+    #[tracing::instrument(name = "Changing the published state of a blog post", skip(self))]
+    pub async fn set_post_published(&self, url_id: &str, publish: bool) -> Result<(), DomainError> {
+        let post = self.get_existing_post(url_id).await?;
+        self.repository
+            .update_post_published(post.id, publish)
+            .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "Pinning or unpinning a blog post", skip(self))]
+    pub async fn set_post_pinned(&self, url_id: &str, pinned: bool) -> Result<(), DomainError> {
+        let post = self.get_existing_post(url_id).await?;
+        self.repository.update_post_pinned(post.id, pinned).await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "Moving a blog post to the trash", skip(self))]
+    pub async fn trash_post(&self, url_id: &str) -> Result<(), DomainError> {
+        let post = self.get_existing_post(url_id).await?;
+        self.repository.update_post_trashed(post.id, true).await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "Restoring a blog post from the trash", skip(self))]
+    pub async fn restore_post(&self, url_id: &str) -> Result<(), DomainError> {
+        let post = self.get_existing_post(url_id).await?;
+        self.repository.update_post_trashed(post.id, false).await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "Permanently deleting a blog post", skip(self))]
+    pub async fn purge_post(&self, url_id: &str) -> Result<(), DomainError> {
+        let post = self.get_existing_post(url_id).await?;
+        if !post.is_trashed() {
+            return Err(DomainError::UserValidationError(
+                "only posts in the trash can be permanently deleted.".to_string(),
+            ));
+        }
+        self.repository.delete_post(post.id).await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "Retrieving the revisions of a blog post", skip(self))]
+    pub async fn get_post_revisions(&self, url_id: &str) -> Result<Vec<PostRevision>, DomainError> {
+        let post = self.get_existing_post(url_id).await?;
+        let revisions = self.repository.load_post_revisions(post.id).await?;
+        Ok(revisions)
+    }
+
+    #[tracing::instrument(name = "Comparing a revision to the current blog post", skip(self))]
+    pub async fn diff_revision(
+        &self,
+        url_id: &str,
+        revision_id: i64,
+    ) -> Result<RevisionDiff, DomainError> {
+        let post = self.get_existing_post(url_id).await?;
+        let revision = self.get_existing_revision(&post, revision_id).await?;
+        let lines = diff_lines(&revision.markdown, &post.markdown);
+
+        Ok(RevisionDiff {
+            revision,
+            current_title: post.title,
+            lines,
+        })
+    }
+
+    #[tracing::instrument(name = "Restoring a blog post revision", skip(self))]
+    pub async fn restore_revision(
+        &self,
+        url_id: &str,
+        revision_id: i64,
+    ) -> Result<(), DomainError> {
+        let post = self.get_existing_post(url_id).await?;
+        let revision = self.get_existing_revision(&post, revision_id).await?;
+
+        // Restoring goes through a regular update so the current version is
+        // kept as a revision as well and the restore itself can be undone.
+        let restored = NewBlogPostData {
+            title: revision.title,
+            url_id: post.url_id.clone(),
+            markdown: revision.markdown,
+            publish: post.is_published() || post.is_scheduled(),
+            publish_at: None,
+            tags: post.tags.clone(),
+            series: post.series.as_ref().map(|series| series.title.clone()),
+            series_part: post.series.as_ref().map(|series| series.part),
+            summary: post.summary.clone(),
+        };
+        let rendered = self
+            .render_post(
+                &restored.markdown,
+                restored.excerpt_markdown(&self.markdown_settings),
+            )
+            .await?;
+        self.repository
+            .update_blog_post(post.id, &restored, &rendered)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_existing_revision(
+        &self,
+        post: &BlogPost,
+        revision_id: i64,
+    ) -> Result<PostRevision, DomainError> {
+        self.repository
+            .load_post_revision(post.id, revision_id)
+            .await?
+            .ok_or(DomainError::RevisionNotFound(revision_id))
+    }
+
+    /// Url ids are unique, so a post can't take one that another post uses.
+    async fn ensure_url_id_available(
+        &self,
+        url_id: &str,
+        post_id: Option<i64>,
+    ) -> Result<(), DomainError> {
+        match self.repository.load_post_by_url_id(url_id).await? {
+            Some(other) if Some(other.id) != post_id => Err(DomainError::UserValidationError(
+                format!("another post already uses the url id {url_id}."),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    async fn get_existing_post(&self, url_id: &str) -> Result<BlogPost, DomainError> {
+        self.repository
+            .load_post_by_url_id(url_id)
+            .await?
+            .ok_or_else(|| DomainError::PostNotFound(url_id.to_string()))
+    }
+
+    /// Renders every post again, e.g. after the markdown settings changed.
+    /// A post that fails to render is logged and skipped. Returns the url ids
+    /// of the posts that failed.
+    #[tracing::instrument(name = "Re-rendering all blog posts", skip(self))]
+    pub async fn rerender_posts(&self) -> Result<Vec<String>, DomainError> {
+        let posts = self.repository.load_all_posts().await?;
+        Ok(self.rerender_each(&posts).await)
+    }
+
+    /// Renders the posts that were stored before excerpts, word counts and
+    /// tables of contents were. A post that fails to render is logged and skipped, so it doesn't
+    /// keep the others from being rendered. Returns the number of posts
+    /// rendered.
+    #[tracing::instrument(name = "Backfilling rendered blog post fields", skip(self))]
+    pub async fn backfill_rendered_posts(&self) -> Result<usize, DomainError> {
+        let mut posts = self.repository.load_unrendered_posts().await?;
+        // Posts stored before tables of contents were, if the renderer finds
+        // the marker in them
+        for post in self.repository.load_posts_without_toc().await? {
+            if !posts.iter().any(|p| p.id == post.id)
+                && markdown::table_of_contents(&post.markdown, &self.markdown_settings).is_some()
+            {
+                posts.push(post);
+            }
+        }
+        let failed = self.rerender_each(&posts).await;
+        Ok(posts.len() - failed.len())
+    }
+
+    /// Renders the posts one by one, logging and skipping the ones that fail.
+    /// Returns the url ids of those.
+    async fn rerender_each(&self, posts: &[BlogPost]) -> Vec<String> {
+        let mut failed = Vec::new();
+        for post in posts {
+            if let Err(e) = self.rerender_post(post).await {
+                tracing::error!("Failed to render blog post {}: {e:?}", post.url_id);
+                failed.push(post.url_id.clone());
+            }
+        }
+        failed
+    }
+
+    async fn rerender_post(&self, post: &BlogPost) -> Result<(), DomainError> {
+        let rendered = self
+            .render_post(
+                &post.markdown,
+                post.excerpt_markdown(&self.markdown_settings),
+            )
+            .await?;
+        self.repository.update_post_html(post.id, &rendered).await?;
         Ok(())
     }
 
+    #[tracing::instrument(name = "Rendering blog post", skip(self, markdown, excerpt_markdown))]
+    async fn render_post(
+        &self,
+        markdown: &str,
+        excerpt_markdown: &str,
+    ) -> Result<RenderedPost, DomainError> {
+        let settings = self.markdown_settings.clone();
+        let highlighting = self.highlighting.clone();
+        let markdown = markdown.to_string();
+        let (html, word_count, toc) = util::spawn_blocking_with_tracing(move || {
+            let html = markdown::convert_to_html(&markdown, &settings, &highlighting.syntax_set)?;
+            let word_count = markdown::word_count(&markdown, &settings);
+            // Rendered along with the HTML so its links match the heading ids
+            let toc = markdown::table_of_contents(&markdown, &settings);
+            anyhow::Ok((html, word_count, toc))
+        })
+        .await
+        .context("Failed to spawn blocking task")
+        .map_err(DomainError::UnexpectedError)??;
+
+        Ok(RenderedPost {
+            html,
+            excerpt_html: self
+                .generate_excerpt_html(excerpt_markdown.to_string())
+                .await?,
+            word_count,
+            toc,
+        })
+    }
+
+    /// The HTML for markdown from the editor, which may still have its front
+    /// matter. Stored posts already have it removed.
+    #[tracing::instrument(name = "Generating preview HTML for markdown", skip(self, markdown))]
+    pub async fn preview_html(&self, markdown: String) -> Result<String, DomainError> {
+        let body = match front_matter::parse(&markdown).map_err(DomainError::UserValidationError)? {
+            Some((_, body)) => body.to_string(),
+            None => markdown,
+        };
+        self.generate_html(body).await
+    }
+
     #[tracing::instrument(name = "Generating HTML for markdown", skip(self, markdown))]
-    pub async fn generate_html(&self, markdown: String) -> Result<String, DomainError> {
-        let html = util::spawn_blocking_with_tracing(move || markdown::convert_to_html(&markdown))
+    async fn generate_html(&self, markdown: String) -> Result<String, DomainError> {
+        self.generate_html_with(markdown, markdown::convert_to_html)
             .await
-            .context("Failed to spawn blocking task")
-            .map_err(DomainError::UnexpectedError)??;
+    }
+
+    #[tracing::instrument(name = "Generating excerpt HTML for markdown", skip(self, markdown))]
+    async fn generate_excerpt_html(&self, markdown: String) -> Result<String, DomainError> {
+        self.generate_html_with(markdown, markdown::convert_excerpt_to_html)
+            .await
+    }
+
+    async fn generate_html_with(
+        &self,
+        markdown: String,
+        convert: fn(&str, &MarkdownSettings, &SyntaxSet) -> anyhow::Result<String>,
+    ) -> Result<String, DomainError> {
+        let settings = self.markdown_settings.clone();
+        let highlighting = self.highlighting.clone();
+        let html = util::spawn_blocking_with_tracing(move || {
+            convert(&markdown, &settings, &highlighting.syntax_set)
+        })
+        .await
+        .context("Failed to spawn blocking task")
+        .map_err(DomainError::UnexpectedError)??;
 
         Ok(html)
     }
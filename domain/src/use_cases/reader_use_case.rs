@@ -1,38 +1,243 @@
-use crate::{BlogPost, DomainError, Repository};
-use chrono::{DateTime, Utc};
+use crate::{
+    ArchiveYear, BlogPost, DomainError, PostOrder, PostQuery, PostSummary, PostsPage, Repository,
+    SearchResult, Series, Tag,
+};
+use chrono::{DateTime, Months, NaiveDate, Utc};
+
+const POSTS_PER_PAGE: u32 = 10;
+/// Maximum number of search results.
+const SEARCH_RESULTS: u32 = 50;
 
 pub struct ReaderUseCase {
     repository: Box<dyn Repository>,
+    /// The order of posts on the index, tag and archive pages.
+    post_order: PostOrder,
 }
 
 impl ReaderUseCase {
-    pub fn new<R>(repository: R) -> Self
+    pub fn new<R>(repository: R, post_order: PostOrder) -> Self
     where
         R: Repository + 'static,
     {
         Self {
             repository: Box::new(repository),
+            post_order,
         }
     }
 
     #[tracing::instrument(name = "Retrieving all published blog posts", skip(self))]
-    pub async fn get_published_posts(&self) -> Result<Vec<BlogPost>, DomainError> {
+    pub async fn get_published_posts(&self) -> Result<Vec<PostSummary>, DomainError> {
+        self.list_published_posts(&PostQuery::default()).await
+    }
+
+    /// The newest `count` published posts, optionally only those with `tag`,
+    /// newest first. Fails like [`Self::get_published_posts_by_tag`] if no
+    /// published post has the tag.
+    #[tracing::instrument(name = "Retrieving the newest published blog posts", skip(self))]
+    pub async fn get_newest_posts(
+        &self,
+        tag: Option<&str>,
+        count: u32,
+    ) -> Result<Vec<BlogPost>, DomainError> {
+        let query = PostQuery {
+            order: PostOrder::NewestFirst,
+            limit: Some(count),
+            ..PostQuery::default()
+        };
+        let summaries = match tag {
+            Some(tag) => self.list_published_posts_with_tag(tag, query).await?,
+            None => self.list_published_posts(&query).await?,
+        };
+        let ids = summaries.iter().map(|post| post.id).collect::<Vec<_>>();
+
+        let mut posts = self.repository.load_posts_by_ids(&ids).await?;
+        posts.sort_by_key(|post| ids.iter().position(|id| *id == post.id));
+        Ok(posts)
+    }
+
+    /// `page` starts at 1.
+    #[tracing::instrument(name = "Retrieving a page of published blog posts", skip(self))]
+    pub async fn get_published_posts_page(&self, page: u32) -> Result<PostsPage, DomainError> {
+        let page = page.max(1);
+        // Load one more post than needed to know if there is a next page
         let mut posts = self
+            .list_published_posts(&PostQuery {
+                order: self.post_order,
+                pinned_first: true,
+                offset: (page - 1).saturating_mul(POSTS_PER_PAGE),
+                limit: Some(POSTS_PER_PAGE + 1),
+                ..PostQuery::default()
+            })
+            .await?;
+
+        if posts.is_empty() && page > 1 {
+            return Err(DomainError::PageNotFound(page));
+        }
+        let has_next = posts.len() > POSTS_PER_PAGE as usize;
+        posts.truncate(POSTS_PER_PAGE as usize);
+
+        Ok(PostsPage {
+            posts,
+            page,
+            has_next,
+        })
+    }
+
+    /// Post counts per year and month, newest first.
+    #[tracing::instrument(name = "Retrieving the archive", skip(self))]
+    pub async fn get_archive(&self) -> Result<Vec<ArchiveYear>, DomainError> {
+        let months = self
             .repository
-            .load_all_posts()
-            .await?
-            .into_iter()
-            .filter(|post| post.is_published())
-            .collect::<Vec<_>>();
+            .count_published_posts_by_month(Utc::now())
+            .await?;
+
+        let mut years = Vec::<ArchiveYear>::new();
+        for month in months {
+            match years.last_mut() {
+                Some(year) if year.year == month.year => {
+                    year.post_count += month.post_count;
+                    year.months.push(month);
+                }
+                _ => years.push(ArchiveYear {
+                    year: month.year,
+                    post_count: month.post_count,
+                    months: vec![month],
+                }),
+            }
+        }
 
-        posts.sort_unstable_by_key(|post| post.published_at().unwrap_or(DateTime::<Utc>::MIN_UTC));
+        Ok(years)
+    }
+
+    /// Posts published in `year`, or only in `month` of `year` if given.
+    #[tracing::instrument(name = "Retrieving archived blog posts", skip(self))]
+    pub async fn get_archived_posts(
+        &self,
+        year: i32,
+        month: Option<u32>,
+    ) -> Result<Vec<PostSummary>, DomainError> {
+        // Both dates are None for a year outside of what chrono supports
+        let from = NaiveDate::from_ymd_opt(year, month.unwrap_or(1), 1);
+        let months = Months::new(if month.is_some() { 1 } else { 12 });
+        let to = from.and_then(|from| from.checked_add_months(months));
+        let (Some(from), Some(to)) = (from, to) else {
+            return Err(DomainError::UserValidationError(
+                "Invalid archive date".to_string(),
+            ));
+        };
 
+        self.list_published_posts(&PostQuery {
+            published_from: Some(start_of_day(from)),
+            published_until: Some(start_of_day(to)),
+            order: self.post_order,
+            ..PostQuery::default()
+        })
+        .await
+    }
+
+    /// Fails if no published post has the tag.
+    #[tracing::instrument(name = "Retrieving published blog posts by tag", skip(self))]
+    pub async fn get_published_posts_by_tag(
+        &self,
+        tag: &str,
+    ) -> Result<Vec<PostSummary>, DomainError> {
+        self.list_published_posts_with_tag(
+            tag,
+            PostQuery {
+                order: self.post_order,
+                ..PostQuery::default()
+            },
+        )
+        .await
+    }
+
+    async fn list_published_posts_with_tag(
+        &self,
+        tag: &str,
+        query: PostQuery,
+    ) -> Result<Vec<PostSummary>, DomainError> {
+        let posts = self
+            .list_published_posts(&PostQuery {
+                tag: Some(tag.to_string()),
+                ..query
+            })
+            .await?;
+
+        if posts.is_empty() {
+            return Err(DomainError::TagNotFound(tag.to_string()));
+        }
         Ok(posts)
     }
 
+    /// All tags used by published posts, sorted by name.
+    #[tracing::instrument(name = "Retrieving all tags", skip(self))]
+    pub async fn get_tags(&self) -> Result<Vec<Tag>, DomainError> {
+        let tags = self
+            .repository
+            .count_published_posts_by_tag(Utc::now())
+            .await?;
+        Ok(tags)
+    }
+
+    /// The series with only its published parts, or `None` if it has none.
+    #[tracing::instrument(name = "Retrieving a series", skip(self))]
+    pub async fn get_series(&self, url_id: &str) -> Result<Option<Series>, DomainError> {
+        let series = self
+            .repository
+            .load_series(url_id)
+            .await?
+            .map(|series| Series {
+                posts: series
+                    .posts
+                    .into_iter()
+                    .filter(|post| post.is_published())
+                    .collect(),
+                ..series
+            })
+            .filter(|series| !series.posts.is_empty());
+
+        Ok(series)
+    }
+
+    #[tracing::instrument(name = "Searching published blog posts", skip(self))]
+    pub async fn search_published_posts(
+        &self,
+        query: &str,
+    ) -> Result<Vec<SearchResult>, DomainError> {
+        if query.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let results = self
+            .repository
+            .search_posts(Utc::now(), query, SEARCH_RESULTS)
+            .await?;
+
+        Ok(results)
+    }
+
     #[tracing::instrument(name = "Find blog post by url id", skip(self))]
     pub async fn get_post_by_url_id(&self, url_id: &str) -> Result<Option<BlogPost>, DomainError> {
-        let post = self.repository.load_post_by_url_id(url_id).await?;
+        let post = self
+            .repository
+            .load_post_by_url_id(url_id)
+            .await?
+            .filter(|post| post.is_published());
         Ok(post)
     }
+
+    async fn list_published_posts(
+        &self,
+        query: &PostQuery,
+    ) -> Result<Vec<PostSummary>, DomainError> {
+        let posts = self
+            .repository
+            .list_published_posts(Utc::now(), query)
+            .await?;
+        Ok(posts)
+    }
+}
+
+fn start_of_day(date: NaiveDate) -> DateTime<Utc> {
+    date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc()
 }
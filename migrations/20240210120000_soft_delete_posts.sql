@@ -0,0 +1,2 @@
+-- Posts in the trash have a deletion time set
+alter table posts add column deleted_at integer; -- unix ts
@@ -0,0 +1,11 @@
+-- Previous versions of edited blog posts
+create table if not exists post_revisions(
+    id integer primary key autoincrement not null,
+    post_id integer not null references posts (id) on delete cascade,
+    title text not null,
+    markdown text not null,
+    created_at integer not null -- unix ts, when this version was replaced
+) strict;
+
+create index index_post_revisions_post_id
+    on post_revisions (post_id);
@@ -0,0 +1,17 @@
+-- Create tag tables
+create table if not exists tags(
+    id integer primary key autoincrement not null,
+    name text not null
+) strict;
+
+create unique index index_tags_name
+    on tags (name);
+
+create table if not exists post_tags(
+    post_id integer not null references posts (id) on delete cascade,
+    tag_id integer not null references tags (id) on delete cascade,
+    primary key (post_id, tag_id)
+) strict;
+
+create index index_post_tags_tag_id
+    on post_tags (tag_id);
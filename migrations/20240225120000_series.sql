@@ -0,0 +1,15 @@
+-- Create series table, posts can optionally be a numbered part of a series
+create table if not exists series(
+    id integer primary key autoincrement not null,
+    url_id text not null,
+    title text not null
+) strict;
+
+create unique index index_series_url_id
+    on series (url_id);
+
+alter table posts add column series_id integer references series (id) on delete set null;
+alter table posts add column series_part integer;
+
+create index index_posts_series_id
+    on posts (series_id);
@@ -0,0 +1,28 @@
+-- Full-text index over the title and markdown of posts
+create virtual table if not exists posts_fts using fts5(
+    title,
+    markdown,
+    content = 'posts',
+    content_rowid = 'id'
+);
+
+-- Keep the index in sync with the posts table
+create trigger posts_fts_after_insert after insert on posts begin
+    insert into posts_fts (rowid, title, markdown)
+    values (new.id, new.title, new.markdown);
+end;
+
+create trigger posts_fts_after_delete after delete on posts begin
+    insert into posts_fts (posts_fts, rowid, title, markdown)
+    values ('delete', old.id, old.title, old.markdown);
+end;
+
+create trigger posts_fts_after_update after update of title, markdown on posts begin
+    insert into posts_fts (posts_fts, rowid, title, markdown)
+    values ('delete', old.id, old.title, old.markdown);
+    insert into posts_fts (rowid, title, markdown)
+    values (new.id, new.title, new.markdown);
+end;
+
+-- Index existing posts
+insert into posts_fts (posts_fts) values ('rebuild');
@@ -0,0 +1,3 @@
+-- Index for listing published posts by publish date
+create index index_posts_is_published_published_at
+    on posts (is_published, published_at);
@@ -0,0 +1,2 @@
+-- Pinned posts are listed first on the index page
+alter table posts add column is_pinned integer not null default 0;
@@ -0,0 +1,4 @@
+-- Summary written by the author and the rendered excerpt shown in listings
+alter table posts add column summary text;
+-- NULL for posts stored before, until they are rendered again
+alter table posts add column excerpt_html text;
@@ -0,0 +1,3 @@
+-- Word count of the post text, for the estimated reading time. NULL for posts
+-- stored before, until they are rendered again
+alter table posts add column word_count integer;
@@ -0,0 +1,4 @@
+-- The headings of posts with a [TOC] marker as a JSON array, NULL for other posts
+-- and for posts stored before, until they are rendered again.
+-- The heading ids depend on the markdown settings the post was rendered with.
+alter table posts add column toc text;
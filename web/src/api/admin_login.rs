@@ -26,7 +26,7 @@ pub async fn admin_login(
         Ok(_) => {
             session.set_is_admin(true);
             Ok(HttpResponse::SeeOther()
-                .insert_header((LOCATION, "/admin/draft"))
+                .insert_header((LOCATION, crate::ROUTE_PAGE_DASHBOARD))
                 .finish())
         }
         Err(e) => {
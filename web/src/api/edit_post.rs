@@ -0,0 +1,14 @@
+use crate::web_error::WebError;
+use actix_web::web::{Form, Redirect};
+use actix_web::{web, Responder};
+use domain::{AdminUseCase, NewBlogPostData};
+
+#[tracing::instrument(name = "Editing an existing blog post", skip(form, admin_uc))]
+pub async fn edit_post(
+    url_id: web::Path<String>,
+    form: Form<NewBlogPostData>,
+    admin_uc: web::Data<AdminUseCase>,
+) -> Result<impl Responder, WebError> {
+    let url_id = admin_uc.update_blog_post(&url_id, &form).await?;
+    Ok(Redirect::to(format!("/blog/{url_id}")).see_other())
+}
@@ -0,0 +1,201 @@
+use crate::api::xml_response;
+use crate::pages::{tag_url, BlogPostAugmentation};
+use crate::site::SiteSettings;
+use crate::web_error::WebError;
+use actix_web::{web, HttpResponse};
+use askama::Template;
+use chrono::{DateTime, Utc};
+use domain::{BlogPost, ReaderUseCase};
+
+/// Maximum number of posts in a feed.
+const FEED_SIZE: u32 = 20;
+
+#[derive(Template)]
+#[template(path = "atom.xml")]
+struct AtomTemplate<'a> {
+    feed: Feed<'a>,
+}
+
+#[derive(Template)]
+#[template(path = "rss.xml")]
+struct RssTemplate<'a> {
+    feed: Feed<'a>,
+}
+
+struct Feed<'a> {
+    title: String,
+    /// The URL of the feed itself.
+    url: String,
+    /// The URL of the HTML page with the same posts.
+    page_url: String,
+    updated: DateTime<Utc>,
+    entries: Vec<FeedEntry<'a>>,
+}
+
+struct FeedEntry<'a> {
+    title: &'a str,
+    url: String,
+    /// With relative URLs resolved, as feed readers would resolve them
+    /// against the feed URL.
+    html: String,
+    markdown: &'a str,
+    excerpt_html: String,
+    summary: String,
+    published: DateTime<Utc>,
+    updated: DateTime<Utc>,
+}
+
+impl<'a> Feed<'a> {
+    fn new(
+        site: &SiteSettings,
+        title: String,
+        path: &str,
+        page_path: &str,
+        posts: &'a [BlogPost],
+    ) -> Self {
+        let entries = posts
+            .iter()
+            .filter_map(|post| {
+                let published = post.published_at()?;
+                let path = post.url();
+                Some(FeedEntry {
+                    title: &post.title,
+                    url: site.absolute_url(&path),
+                    html: site.resolve_urls_in_html(&path, &post.html),
+                    markdown: &post.markdown,
+                    excerpt_html: site.resolve_urls_in_html(&path, &post.excerpt_html),
+                    summary: post.description(&site.markdown),
+                    published,
+                    updated: post.updated_at.unwrap_or(published).max(published),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        Self {
+            title,
+            url: site.absolute_url(path),
+            page_url: site.absolute_url(page_path),
+            updated: entries
+                .iter()
+                .map(|entry| entry.updated)
+                .max()
+                .unwrap_or(DateTime::<Utc>::UNIX_EPOCH),
+            entries,
+        }
+    }
+}
+
+/// See https://www.jsonfeed.org/version/1.1/
+#[derive(serde::Serialize)]
+struct JsonFeed<'a> {
+    version: &'static str,
+    title: &'a str,
+    home_page_url: &'a str,
+    feed_url: &'a str,
+    items: Vec<JsonFeedItem<'a>>,
+}
+
+#[derive(serde::Serialize)]
+struct JsonFeedItem<'a> {
+    id: &'a str,
+    url: &'a str,
+    title: &'a str,
+    content_html: &'a str,
+    content_text: &'a str,
+    summary: &'a str,
+    date_published: String,
+    date_modified: String,
+}
+
+impl<'a> From<&'a Feed<'a>> for JsonFeed<'a> {
+    fn from(feed: &'a Feed<'a>) -> Self {
+        Self {
+            version: "https://jsonfeed.org/version/1.1",
+            title: &feed.title,
+            home_page_url: &feed.page_url,
+            feed_url: &feed.url,
+            items: feed
+                .entries
+                .iter()
+                .map(|entry| JsonFeedItem {
+                    id: &entry.url,
+                    url: &entry.url,
+                    title: entry.title,
+                    content_html: &entry.html,
+                    content_text: entry.markdown,
+                    summary: &entry.summary,
+                    date_published: entry.published.to_rfc3339(),
+                    date_modified: entry.updated.to_rfc3339(),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[tracing::instrument(name = "Serving the Atom feed", skip(reader_uc, site))]
+pub async fn atom_feed(
+    reader_uc: web::Data<ReaderUseCase>,
+    site: web::Data<SiteSettings>,
+) -> Result<HttpResponse, WebError> {
+    let posts = reader_uc.get_newest_posts(None, FEED_SIZE).await?;
+    let feed = Feed::new(&site, site.title.clone(), "/feed.atom", "/", &posts);
+    xml_response(AtomTemplate { feed }, "application/atom+xml")
+}
+
+#[tracing::instrument(name = "Serving the RSS feed", skip(reader_uc, site))]
+pub async fn rss_feed(
+    reader_uc: web::Data<ReaderUseCase>,
+    site: web::Data<SiteSettings>,
+) -> Result<HttpResponse, WebError> {
+    let posts = reader_uc.get_newest_posts(None, FEED_SIZE).await?;
+    let feed = Feed::new(&site, site.title.clone(), "/feed.rss", "/", &posts);
+    xml_response(RssTemplate { feed }, "application/rss+xml")
+}
+
+#[tracing::instrument(name = "Serving the JSON feed", skip(reader_uc, site))]
+pub async fn json_feed(
+    reader_uc: web::Data<ReaderUseCase>,
+    site: web::Data<SiteSettings>,
+) -> Result<HttpResponse, WebError> {
+    let posts = reader_uc.get_newest_posts(None, FEED_SIZE).await?;
+    let feed = Feed::new(&site, site.title.clone(), "/feed.json", "/", &posts);
+    Ok(HttpResponse::Ok()
+        .content_type("application/feed+json; charset=utf-8")
+        .json(JsonFeed::from(&feed)))
+}
+
+#[tracing::instrument(name = "Serving the Atom feed for a tag", skip(reader_uc, site))]
+pub async fn tag_atom_feed(
+    reader_uc: web::Data<ReaderUseCase>,
+    site: web::Data<SiteSettings>,
+    tag: web::Path<String>,
+) -> Result<HttpResponse, WebError> {
+    let posts = reader_uc.get_newest_posts(Some(&tag), FEED_SIZE).await?;
+    let page_path = tag_url(&tag);
+    let feed = Feed::new(
+        &site,
+        format!("{} - {tag}", site.title),
+        &format!("{page_path}/feed.atom"),
+        &page_path,
+        &posts,
+    );
+    xml_response(AtomTemplate { feed }, "application/atom+xml")
+}
+
+#[tracing::instrument(name = "Serving the RSS feed for a tag", skip(reader_uc, site))]
+pub async fn tag_rss_feed(
+    reader_uc: web::Data<ReaderUseCase>,
+    site: web::Data<SiteSettings>,
+    tag: web::Path<String>,
+) -> Result<HttpResponse, WebError> {
+    let posts = reader_uc.get_newest_posts(Some(&tag), FEED_SIZE).await?;
+    let page_path = tag_url(&tag);
+    let feed = Feed::new(
+        &site,
+        format!("{} - {tag}", site.title),
+        &format!("{page_path}/feed.rss"),
+        &page_path,
+        &posts,
+    );
+    xml_response(RssTemplate { feed }, "application/rss+xml")
+}
@@ -1,16 +1,26 @@
-use domain::BlogPost;
+use crate::web_error::WebError;
+use actix_web::HttpResponse;
+use anyhow::Context;
+use askama::Template;
+use domain::DomainError;
 
 pub(crate) mod admin_login;
+pub(crate) mod edit_post;
+pub(crate) mod feed;
 pub(crate) mod health_check;
 pub(crate) mod new_post;
+pub(crate) mod post_status;
+pub(crate) mod revisions;
+pub(crate) mod sitemap;
 pub(crate) mod r#static;
 
-pub trait BlogPostAugmentation {
-    fn url(&self) -> String;
-}
+fn xml_response<T: Template>(template: T, content_type: &str) -> Result<HttpResponse, WebError> {
+    let body = template
+        .render()
+        .context("Failed to render XML template")
+        .map_err(DomainError::UnexpectedError)?;
 
-impl BlogPostAugmentation for BlogPost {
-    fn url(&self) -> String {
-        format!("/blog/{}", &self.url_id)
-    }
+    Ok(HttpResponse::Ok()
+        .content_type(format!("{content_type}; charset=utf-8"))
+        .body(body))
 }
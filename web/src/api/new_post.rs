@@ -11,8 +11,8 @@ pub async fn new_post(
     form: Form<NewBlogPostData>,
     admin_uc: web::Data<AdminUseCase>,
 ) -> Result<impl Responder, WebError> {
-    admin_uc.store_blog_post(&form).await?;
-    Ok(Redirect::to(format!("/blog/{}", form.url_id)).see_other())
+    let url_id = admin_uc.store_blog_post(&form).await?;
+    Ok(Redirect::to(format!("/blog/{url_id}")).see_other())
 }
 
 #[derive(serde::Deserialize, Debug)]
@@ -25,7 +25,7 @@ pub async fn preview_html(
     form: Form<PreviewPostData>,
     admin_uc: web::Data<AdminUseCase>,
 ) -> Result<impl Responder, WebError> {
-    let html = admin_uc.generate_html(form.0.markdown).await?;
+    let html = admin_uc.preview_html(form.0.markdown).await?;
     Ok(HttpResponseBuilder::new(StatusCode::OK)
         .content_type(ContentType::html())
         .body(html))
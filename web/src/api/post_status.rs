@@ -0,0 +1,77 @@
+use crate::web_error::WebError;
+use actix_web::web::Redirect;
+use actix_web::{web, Responder};
+use actix_web_flash_messages::FlashMessage;
+use domain::AdminUseCase;
+
+#[tracing::instrument(name = "Publishing a blog post", skip(admin_uc))]
+pub async fn publish_post(
+    url_id: web::Path<String>,
+    admin_uc: web::Data<AdminUseCase>,
+) -> Result<impl Responder, WebError> {
+    admin_uc.set_post_published(&url_id, true).await?;
+    Ok(Redirect::to(format!("/admin/edit/{url_id}")).see_other())
+}
+
+#[tracing::instrument(name = "Unpublishing a blog post", skip(admin_uc))]
+pub async fn unpublish_post(
+    url_id: web::Path<String>,
+    admin_uc: web::Data<AdminUseCase>,
+) -> Result<impl Responder, WebError> {
+    admin_uc.set_post_published(&url_id, false).await?;
+    Ok(Redirect::to(format!("/admin/edit/{url_id}")).see_other())
+}
+
+#[tracing::instrument(name = "Pinning a blog post", skip(admin_uc))]
+pub async fn pin_post(
+    url_id: web::Path<String>,
+    admin_uc: web::Data<AdminUseCase>,
+) -> Result<impl Responder, WebError> {
+    admin_uc.set_post_pinned(&url_id, true).await?;
+    Ok(Redirect::to(format!("/admin/edit/{url_id}")).see_other())
+}
+
+#[tracing::instrument(name = "Unpinning a blog post", skip(admin_uc))]
+pub async fn unpin_post(
+    url_id: web::Path<String>,
+    admin_uc: web::Data<AdminUseCase>,
+) -> Result<impl Responder, WebError> {
+    admin_uc.set_post_pinned(&url_id, false).await?;
+    Ok(Redirect::to(format!("/admin/edit/{url_id}")).see_other())
+}
+
+#[tracing::instrument(name = "Trashing a blog post", skip(admin_uc))]
+pub async fn trash_post(
+    url_id: web::Path<String>,
+    admin_uc: web::Data<AdminUseCase>,
+) -> Result<impl Responder, WebError> {
+    admin_uc.trash_post(&url_id).await?;
+    Ok(Redirect::to(crate::ROUTE_PAGE_TRASH).see_other())
+}
+
+#[tracing::instrument(name = "Restoring a blog post", skip(admin_uc))]
+pub async fn restore_post(
+    url_id: web::Path<String>,
+    admin_uc: web::Data<AdminUseCase>,
+) -> Result<impl Responder, WebError> {
+    admin_uc.restore_post(&url_id).await?;
+    Ok(Redirect::to(crate::ROUTE_PAGE_TRASH).see_other())
+}
+
+#[tracing::instrument(name = "Purging a blog post", skip(admin_uc))]
+pub async fn purge_post(
+    url_id: web::Path<String>,
+    admin_uc: web::Data<AdminUseCase>,
+) -> Result<impl Responder, WebError> {
+    admin_uc.purge_post(&url_id).await?;
+    Ok(Redirect::to(crate::ROUTE_PAGE_TRASH).see_other())
+}
+
+#[tracing::instrument(name = "Re-rendering all blog posts", skip(admin_uc))]
+pub async fn rerender_posts(admin_uc: web::Data<AdminUseCase>) -> Result<impl Responder, WebError> {
+    let failed = admin_uc.rerender_posts().await?;
+    if !failed.is_empty() {
+        FlashMessage::error(format!("Failed to render posts: {}", failed.join(", "))).send();
+    }
+    Ok(Redirect::to(crate::ROUTE_PAGE_DASHBOARD).see_other())
+}
@@ -0,0 +1,14 @@
+use crate::web_error::WebError;
+use actix_web::web::Redirect;
+use actix_web::{web, Responder};
+use domain::AdminUseCase;
+
+#[tracing::instrument(name = "Restoring a blog post revision", skip(admin_uc))]
+pub async fn restore_revision(
+    path: web::Path<(String, i64)>,
+    admin_uc: web::Data<AdminUseCase>,
+) -> Result<impl Responder, WebError> {
+    let (url_id, revision_id) = path.into_inner();
+    admin_uc.restore_revision(&url_id, revision_id).await?;
+    Ok(Redirect::to(format!("/admin/edit/{url_id}")).see_other())
+}
@@ -0,0 +1,56 @@
+use crate::api::xml_response;
+use crate::pages::BlogPostAugmentation;
+use crate::site::SiteSettings;
+use crate::web_error::WebError;
+use actix_web::{web, HttpResponse};
+use askama::Template;
+use chrono::{DateTime, Utc};
+use domain::ReaderUseCase;
+
+#[derive(Template)]
+#[template(path = "sitemap.xml")]
+struct SitemapTemplate {
+    urls: Vec<SitemapUrl>,
+}
+
+struct SitemapUrl {
+    loc: String,
+    lastmod: Option<DateTime<Utc>>,
+}
+
+#[tracing::instrument(name = "Serving the sitemap", skip(reader_uc, site))]
+pub async fn sitemap(
+    reader_uc: web::Data<ReaderUseCase>,
+    site: web::Data<SiteSettings>,
+) -> Result<HttpResponse, WebError> {
+    let posts = reader_uc.get_published_posts().await?;
+
+    let mut urls = vec![SitemapUrl {
+        loc: site.absolute_url("/"),
+        lastmod: posts
+            .iter()
+            .filter_map(|post| post.updated_at.max(post.published_at()))
+            .max(),
+    }];
+    urls.extend(posts.iter().map(|post| SitemapUrl {
+        loc: site.absolute_url(&post.url()),
+        lastmod: post.updated_at.max(post.published_at()),
+    }));
+
+    xml_response(SitemapTemplate { urls }, "application/xml")
+}
+
+#[tracing::instrument(name = "Serving robots.txt", skip(site))]
+pub async fn robots_txt(site: web::Data<SiteSettings>) -> HttpResponse {
+    let body = match &site.robots_txt {
+        Some(robots_txt) => robots_txt.clone(),
+        None => format!(
+            "User-agent: *\nDisallow: /admin/\n\nSitemap: {}\n",
+            site.absolute_url("/sitemap.xml")
+        ),
+    };
+
+    HttpResponse::Ok()
+        .content_type("text/plain; charset=utf-8")
+        .body(body)
+}
@@ -1,19 +1,23 @@
-use actix_web::HttpResponse;
+use actix_web::{web, HttpResponse};
+use domain::SyntaxHighlighting;
 
-pub async fn css_dark() -> HttpResponse {
+pub async fn css_dark(highlighting: web::Data<SyntaxHighlighting>) -> HttpResponse {
     HttpResponse::Ok()
         .content_type("text/css; charset=utf-8")
-        .body(include_str!("../../css/Solarized (dark)-edit.css"))
+        .body(highlighting.dark_css().to_string())
 }
 
-pub async fn css_light() -> HttpResponse {
+pub async fn css_light(highlighting: web::Data<SyntaxHighlighting>) -> HttpResponse {
     HttpResponse::Ok()
         .content_type("text/css; charset=utf-8")
-        .body(include_str!("../../css/InspiredGitHub-edit.css"))
+        .body(highlighting.light_css().to_string())
 }
 
 pub async fn css_base() -> HttpResponse {
     HttpResponse::Ok()
         .content_type("text/css; charset=utf-8")
-        .body(include_str!("../../css/water.css"))
+        .body(concat!(
+            include_str!("../../css/water.css"),
+            include_str!("../../css/mimir.css")
+        ))
 }
@@ -1,6 +1,7 @@
 mod api;
 mod pages;
 pub(crate) mod session;
+pub mod site;
 pub mod startup;
 pub(crate) mod web_error;
 
@@ -20,3 +21,5 @@ pub static DEV_SCRIPTS: &str = "";
 pub static ROUTE_API_LOGIN: &str = "/login";
 pub static ROUTE_API_NEW_POST: &str = "/admin/new_post";
 pub static ROUTE_API_PREVIEW_HTML: &str = "/admin/preview_html";
+pub static ROUTE_PAGE_DASHBOARD: &str = "/admin/posts";
+pub static ROUTE_PAGE_TRASH: &str = "/admin/trash";
@@ -1,17 +1,74 @@
-use actix_web::HttpResponse;
+use crate::pages::BlogPostAugmentation;
+use crate::web_error::WebError;
+use actix_web::{web, HttpResponse};
 use askama::Template;
 use askama_actix::TemplateToResponse;
+use domain::{AdminUseCase, BlogPost, DomainError};
 
 #[derive(Template)]
 #[template(path = "admin_draft.html")]
-struct HelloTemplate<'a> {
+struct DraftTemplate<'a> {
     title: &'a str,
+    action: &'a str,
+    post_title: &'a str,
+    url_id: &'a str,
+    markdown: &'a str,
+    publish: bool,
+    publish_at: String,
+    tags: String,
+    series: &'a str,
+    series_part: String,
+    summary: &'a str,
+    post: Option<&'a BlogPost>,
 }
 
 #[tracing::instrument(name = "Serving the admin post page")]
 pub async fn draft_post_page() -> HttpResponse {
-    HelloTemplate {
+    DraftTemplate {
         title: "Admin - New Post",
+        action: crate::ROUTE_API_NEW_POST,
+        post_title: "",
+        url_id: "",
+        markdown: "",
+        publish: true,
+        publish_at: String::new(),
+        tags: String::new(),
+        series: "",
+        series_part: String::new(),
+        summary: "",
+        post: None,
     }
     .to_response()
 }
+
+#[tracing::instrument(name = "Serving the admin edit post page", skip(admin_uc))]
+pub async fn edit_post_page(
+    admin_uc: web::Data<AdminUseCase>,
+    url_id: web::Path<String>,
+) -> Result<HttpResponse, WebError> {
+    let url_id = url_id.into_inner();
+    let post = admin_uc
+        .get_post_by_url_id(&url_id)
+        .await?
+        .ok_or(DomainError::PostNotFound(url_id))?;
+
+    Ok(DraftTemplate {
+        title: "Admin - Edit Post",
+        action: &post.edit_url(),
+        post_title: &post.title,
+        url_id: &post.url_id,
+        markdown: &post.markdown,
+        publish: post.is_published() || post.is_scheduled(),
+        publish_at: post.publish_at_input_value(),
+        tags: post.tags.join(", "),
+        series: post.series.as_ref().map_or("", |s| &s.title),
+        series_part: post
+            .series
+            .as_ref()
+            .map(|s| s.part.to_string())
+            .unwrap_or_default(),
+        summary: post.summary.as_deref().unwrap_or_default(),
+        post: Some(&post),
+    }
+    .to_response())
+}
@@ -0,0 +1,42 @@
+use crate::pages::BlogPostAugmentation;
+use crate::web_error::WebError;
+use actix_web::{web, HttpResponse};
+use actix_web_flash_messages::{IncomingFlashMessages, Level};
+use askama::Template;
+use askama_actix::TemplateToResponse;
+use domain::{AdminUseCase, BlogPost, PostStatusFilter};
+
+#[derive(Template)]
+#[template(path = "admin_posts.html")]
+struct DashboardTemplate<'a> {
+    title: &'static str,
+    errors: Vec<&'a str>,
+    filter: Option<PostStatusFilter>,
+    posts: Vec<BlogPost>,
+}
+
+#[derive(serde::Deserialize, Debug)]
+pub struct DashboardQuery {
+    status: Option<PostStatusFilter>,
+}
+
+#[tracing::instrument(name = "Serving the admin dashboard", skip(admin_uc, flash))]
+pub async fn admin_posts_page(
+    admin_uc: web::Data<AdminUseCase>,
+    query: web::Query<DashboardQuery>,
+    flash: IncomingFlashMessages,
+) -> Result<HttpResponse, WebError> {
+    let filter = query.into_inner().status;
+    let posts = admin_uc.get_posts(filter).await?;
+    Ok(DashboardTemplate {
+        title: "Admin - Dashboard",
+        errors: flash
+            .iter()
+            .filter(|m| m.level() == Level::Error)
+            .map(|m| m.content())
+            .collect(),
+        filter,
+        posts,
+    }
+    .to_response())
+}
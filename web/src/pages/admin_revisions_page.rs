@@ -0,0 +1,58 @@
+use crate::pages::BlogPostAugmentation;
+use crate::web_error::WebError;
+use actix_web::{web, HttpResponse};
+use askama::Template;
+use askama_actix::TemplateToResponse;
+use domain::{AdminUseCase, BlogPost, DiffLine, DomainError, PostRevision, RevisionDiff};
+
+#[derive(Template)]
+#[template(path = "admin_revisions.html")]
+struct RevisionsTemplate<'a> {
+    title: &'a str,
+    post: &'a BlogPost,
+    revisions: Vec<PostRevision>,
+}
+
+#[derive(Template)]
+#[template(path = "admin_revision_diff.html")]
+struct RevisionDiffTemplate<'a> {
+    title: &'a str,
+    url_id: &'a str,
+    diff: RevisionDiff,
+}
+
+#[tracing::instrument(name = "Serving the admin revisions page", skip(admin_uc))]
+pub async fn revisions_page(
+    admin_uc: web::Data<AdminUseCase>,
+    url_id: web::Path<String>,
+) -> Result<HttpResponse, WebError> {
+    let url_id = url_id.into_inner();
+    let revisions = admin_uc.get_post_revisions(&url_id).await?;
+    let post = admin_uc
+        .get_post_by_url_id(&url_id)
+        .await?
+        .ok_or(DomainError::PostNotFound(url_id))?;
+
+    Ok(RevisionsTemplate {
+        title: "Admin - Revisions",
+        post: &post,
+        revisions,
+    }
+    .to_response())
+}
+
+#[tracing::instrument(name = "Serving the admin revision diff page", skip(admin_uc))]
+pub async fn revision_diff_page(
+    admin_uc: web::Data<AdminUseCase>,
+    path: web::Path<(String, i64)>,
+) -> Result<HttpResponse, WebError> {
+    let (url_id, revision_id) = path.into_inner();
+    let diff = admin_uc.diff_revision(&url_id, revision_id).await?;
+
+    Ok(RevisionDiffTemplate {
+        title: "Admin - Revision diff",
+        url_id: &url_id,
+        diff,
+    }
+    .to_response())
+}
@@ -0,0 +1,23 @@
+use crate::pages::BlogPostAugmentation;
+use crate::web_error::WebError;
+use actix_web::{web, HttpResponse};
+use askama::Template;
+use askama_actix::TemplateToResponse;
+use domain::{AdminUseCase, BlogPost};
+
+#[derive(Template)]
+#[template(path = "admin_trash.html")]
+struct TrashTemplate {
+    title: &'static str,
+    posts: Vec<BlogPost>,
+}
+
+#[tracing::instrument(name = "Serving the admin trash page", skip(admin_uc))]
+pub async fn trash_page(admin_uc: web::Data<AdminUseCase>) -> Result<HttpResponse, WebError> {
+    let posts = admin_uc.get_trashed_posts().await?;
+    Ok(TrashTemplate {
+        title: "Admin - Trash",
+        posts,
+    }
+    .to_response())
+}
@@ -0,0 +1,78 @@
+use crate::pages::BlogPostAugmentation;
+use crate::web_error::WebError;
+use actix_web::{web, HttpResponse};
+use askama::Template;
+use askama_actix::TemplateToResponse;
+use domain::{ArchiveMonth, ArchiveYear, PostSummary, ReaderUseCase};
+
+#[derive(Template)]
+#[template(path = "archive.html")]
+struct ArchiveTemplate {
+    title: &'static str,
+    years: Vec<ArchiveYear>,
+}
+
+#[derive(Template)]
+#[template(path = "archive_period.html")]
+struct ArchivePeriodTemplate<'a> {
+    title: &'a str,
+    year: i32,
+    months: Vec<ArchiveMonth>,
+    posts: Vec<PostSummary>,
+}
+
+#[tracing::instrument(name = "Serving the archive page", skip(reader_uc))]
+pub async fn archive_page(reader_uc: web::Data<ReaderUseCase>) -> Result<HttpResponse, WebError> {
+    let years = reader_uc.get_archive().await?;
+    Ok(ArchiveTemplate {
+        title: "Archive",
+        years,
+    }
+    .to_response())
+}
+
+#[tracing::instrument(name = "Serving the archive page for a year", skip(reader_uc))]
+pub async fn archive_year_page(
+    reader_uc: web::Data<ReaderUseCase>,
+    year: web::Path<i32>,
+) -> Result<HttpResponse, WebError> {
+    let year = year.into_inner();
+    let posts = reader_uc.get_archived_posts(year, None).await?;
+    let months = reader_uc
+        .get_archive()
+        .await?
+        .into_iter()
+        .find(|archive_year| archive_year.year == year)
+        .map(|archive_year| archive_year.months)
+        .unwrap_or_default();
+
+    Ok(ArchivePeriodTemplate {
+        title: &year.to_string(),
+        year,
+        months,
+        posts,
+    }
+    .to_response())
+}
+
+#[tracing::instrument(name = "Serving the archive page for a month", skip(reader_uc))]
+pub async fn archive_month_page(
+    reader_uc: web::Data<ReaderUseCase>,
+    path: web::Path<(i32, u32)>,
+) -> Result<HttpResponse, WebError> {
+    let (year, month) = path.into_inner();
+    let posts = reader_uc.get_archived_posts(year, Some(month)).await?;
+    let month = ArchiveMonth {
+        year,
+        month,
+        post_count: posts.len(),
+    };
+
+    Ok(ArchivePeriodTemplate {
+        title: &format!("{} {year}", month.name()),
+        year,
+        months: Vec::new(),
+        posts,
+    }
+    .to_response())
+}
@@ -1,25 +1,68 @@
 use crate::pages::BlogPostAugmentation;
+use crate::site::SiteSettings;
 use crate::web_error::WebError;
+use actix_web::http::header;
 use actix_web::{web, HttpResponse};
 use askama::Template;
 use askama_actix::TemplateToResponse;
-use domain::{BlogPost, ReaderUseCase};
+use domain::{DomainError, PostsPage, ReaderUseCase};
 
 #[derive(Template)]
 #[template(path = "index.html")]
 struct PostsTemplate {
     title: &'static str,
-    posts: Vec<BlogPost>,
+    page: PostsPage,
+    previous_url: Option<String>,
+    next_url: Option<String>,
 }
 
-#[tracing::instrument(name = "Serving the admin post page", skip(reader_uc))]
+#[derive(serde::Deserialize, Debug)]
+pub struct PageQuery {
+    page: Option<u32>,
+}
+
+fn page_url(page: u32) -> String {
+    if page <= 1 {
+        "/".to_string()
+    } else {
+        format!("/?page={page}")
+    }
+}
+
+#[tracing::instrument(name = "Serving the index page", skip(reader_uc, site))]
 pub async fn blog_posts_page(
     reader_uc: web::Data<ReaderUseCase>,
+    site: web::Data<SiteSettings>,
+    query: web::Query<PageQuery>,
 ) -> Result<HttpResponse, WebError> {
-    let posts = reader_uc.get_published_posts().await?;
-    Ok(PostsTemplate {
+    let page = reader_uc
+        .get_published_posts_page(query.page.unwrap_or(1))
+        .await?;
+    let template = PostsTemplate {
         title: "Articles",
-        posts,
+        previous_url: page.has_previous().then(|| page_url(page.page - 1)),
+        next_url: page.has_next.then(|| page_url(page.page + 1)),
+        page,
+    };
+
+    let links = [
+        ("prev", &template.previous_url),
+        ("next", &template.next_url),
+    ]
+    .into_iter()
+    .filter_map(|(rel, url)| {
+        url.as_deref()
+            .map(|url| format!("<{}>; rel=\"{rel}\"", site.absolute_url(url)))
+    })
+    .collect::<Vec<_>>();
+
+    let mut response = template.to_response();
+    if !links.is_empty() {
+        response.headers_mut().insert(
+            header::LINK,
+            header::HeaderValue::from_str(&links.join(", "))
+                .map_err(|e| DomainError::UnexpectedError(e.into()))?,
+        );
     }
-    .to_response())
+    Ok(response)
 }
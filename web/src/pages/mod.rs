@@ -1,23 +1,105 @@
-use domain::BlogPost;
+use chrono::{DateTime, Utc};
+use domain::{BlogPost, BlogPostStatus, PostSummary};
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
 
 pub(crate) mod admin_draft_page;
+pub(crate) mod admin_posts_page;
+pub(crate) mod admin_revisions_page;
+pub(crate) mod admin_trash_page;
+pub(crate) mod archive_page;
 pub(crate) mod index_page;
 pub(crate) mod login_page;
+pub(crate) mod search_page;
+pub(crate) mod series_page;
+pub(crate) mod tags_page;
 pub(crate) mod view_post_page;
 
-pub trait BlogPostAugmentation {
-    fn url(&self) -> String;
-    fn published_at_string(&self) -> String;
+/// Everything but the unreserved characters of RFC 3986, so that e.g. a `/`
+/// in a tag doesn't end the path segment.
+const PATH_SEGMENT_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// The path of the page that lists the posts with the tag.
+pub(crate) fn tag_url(tag: &str) -> String {
+    format!("/tags/{}", utf8_percent_encode(tag, PATH_SEGMENT_SET))
 }
 
-impl BlogPostAugmentation for BlogPost {
+pub trait BlogPostAugmentation {
+    fn post_url_id(&self) -> &str;
+    fn post_status(&self) -> &BlogPostStatus;
+    fn post_updated_at(&self) -> Option<DateTime<Utc>>;
+
     fn url(&self) -> String {
-        format!("/blog/{}", &self.url_id)
+        format!("/blog/{}", self.post_url_id())
+    }
+
+    fn edit_url(&self) -> String {
+        format!("/admin/edit/{}", self.post_url_id())
+    }
+
+    fn admin_action_url(&self, action: &str) -> String {
+        format!("/admin/posts/{}/{}", self.post_url_id(), action)
     }
 
     fn published_at_string(&self) -> String {
-        self.published_at()
+        self.post_status()
+            .published_at()
+            .map(|dt| dt.to_string())
+            .unwrap_or_default()
+    }
+
+    fn updated_at_string(&self) -> String {
+        self.post_updated_at()
             .map(|dt| dt.to_string())
             .unwrap_or_default()
     }
+
+    fn status_string(&self) -> String {
+        match self.post_status() {
+            BlogPostStatus::Published(_) => "Published".to_string(),
+            BlogPostStatus::Scheduled(dt) => format!("Scheduled for {dt}"),
+            BlogPostStatus::Unpublished => "Draft".to_string(),
+            BlogPostStatus::Trashed(_) => "Trashed".to_string(),
+        }
+    }
+
+    // Only scheduled posts get their date pre-filled, so that saving a
+    // published post doesn't truncate its publish date to whole minutes.
+    fn publish_at_input_value(&self) -> String {
+        match self.post_status() {
+            BlogPostStatus::Scheduled(dt) => dt.format("%Y-%m-%dT%H:%M").to_string(),
+            _ => String::new(),
+        }
+    }
+}
+
+impl BlogPostAugmentation for BlogPost {
+    fn post_url_id(&self) -> &str {
+        &self.url_id
+    }
+
+    fn post_status(&self) -> &BlogPostStatus {
+        &self.status
+    }
+
+    fn post_updated_at(&self) -> Option<DateTime<Utc>> {
+        self.updated_at
+    }
+}
+
+impl BlogPostAugmentation for PostSummary {
+    fn post_url_id(&self) -> &str {
+        &self.url_id
+    }
+
+    fn post_status(&self) -> &BlogPostStatus {
+        &self.status
+    }
+
+    fn post_updated_at(&self) -> Option<DateTime<Utc>> {
+        self.updated_at
+    }
 }
@@ -0,0 +1,35 @@
+use crate::pages::BlogPostAugmentation;
+use crate::web_error::WebError;
+use actix_web::{web, HttpResponse};
+use askama::Template;
+use askama_actix::TemplateToResponse;
+use domain::{ReaderUseCase, SearchResult};
+
+#[derive(Template)]
+#[template(path = "search.html")]
+struct SearchTemplate<'a> {
+    title: &'a str,
+    query: &'a str,
+    results: Vec<SearchResult>,
+}
+
+#[derive(serde::Deserialize, Debug)]
+pub struct SearchQuery {
+    #[serde(default)]
+    q: String,
+}
+
+#[tracing::instrument(name = "Serving the search page", skip(reader_uc))]
+pub async fn search_page(
+    reader_uc: web::Data<ReaderUseCase>,
+    query: web::Query<SearchQuery>,
+) -> Result<HttpResponse, WebError> {
+    let query = query.into_inner().q;
+    let results = reader_uc.search_published_posts(&query).await?;
+    Ok(SearchTemplate {
+        title: "Search",
+        query: &query,
+        results,
+    }
+    .to_response())
+}
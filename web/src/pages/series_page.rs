@@ -0,0 +1,30 @@
+use crate::pages::BlogPostAugmentation;
+use crate::web_error::WebError;
+use actix_web::http::StatusCode;
+use actix_web::{web, HttpResponse};
+use askama::Template;
+use askama_actix::TemplateToResponse;
+use domain::{ReaderUseCase, Series};
+
+#[derive(Template)]
+#[template(path = "series.html")]
+struct SeriesTemplate<'a> {
+    title: &'a str,
+    series: &'a Series,
+}
+
+#[tracing::instrument(name = "Serving the series page", skip(reader_uc))]
+pub async fn series_page(
+    reader_uc: web::Data<ReaderUseCase>,
+    url_id: web::Path<String>,
+) -> Result<HttpResponse, WebError> {
+    if let Some(series) = reader_uc.get_series(&url_id.into_inner()).await? {
+        Ok(SeriesTemplate {
+            title: &series.title,
+            series: &series,
+        }
+        .to_response())
+    } else {
+        Ok(HttpResponse::new(StatusCode::NOT_FOUND))
+    }
+}
@@ -0,0 +1,46 @@
+use crate::pages::BlogPostAugmentation;
+use crate::web_error::WebError;
+use actix_web::{web, HttpResponse};
+use askama::Template;
+use askama_actix::TemplateToResponse;
+use domain::{PostSummary, ReaderUseCase, Tag};
+
+#[derive(Template)]
+#[template(path = "tags.html")]
+struct TagsTemplate {
+    title: &'static str,
+    tags: Vec<Tag>,
+}
+
+#[derive(Template)]
+#[template(path = "tag.html")]
+struct TagTemplate<'a> {
+    title: &'a str,
+    tag: &'a str,
+    posts: Vec<PostSummary>,
+}
+
+#[tracing::instrument(name = "Serving the tags page", skip(reader_uc))]
+pub async fn tags_page(reader_uc: web::Data<ReaderUseCase>) -> Result<HttpResponse, WebError> {
+    let tags = reader_uc.get_tags().await?;
+    Ok(TagsTemplate {
+        title: "Tags",
+        tags,
+    }
+    .to_response())
+}
+
+#[tracing::instrument(name = "Serving the posts for a tag", skip(reader_uc))]
+pub async fn tag_page(
+    reader_uc: web::Data<ReaderUseCase>,
+    tag: web::Path<String>,
+) -> Result<HttpResponse, WebError> {
+    let tag = tag.into_inner();
+    let posts = reader_uc.get_published_posts_by_tag(&tag).await?;
+    Ok(TagTemplate {
+        title: &format!("Posts tagged {tag}"),
+        tag: &tag,
+        posts,
+    }
+    .to_response())
+}
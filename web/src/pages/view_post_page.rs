@@ -1,29 +1,101 @@
+use crate::pages::BlogPostAugmentation;
+use crate::session::TypedSession;
+use crate::site::SiteSettings;
 use crate::web_error::WebError;
 use actix_web::http::StatusCode;
 use actix_web::{web, HttpResponse};
 use askama::Template;
 use askama_actix::TemplateToResponse;
-use domain::{BlogPost, ReaderUseCase};
+use domain::{AdminUseCase, BlogPost, ReaderUseCase, Series, TocEntry};
 
 #[derive(Template)]
 #[template(path = "view_post.html")]
 struct PostTemplate<'a> {
     title: &'a str,
     post: &'a BlogPost,
+    series: Option<SeriesNavigation<'a>>,
+    meta: PostMeta<'a>,
+    toc: Option<&'a [TocEntry]>,
 }
 
-#[tracing::instrument(name = "Viewing post", skip(reader_uc))]
+/// Used for the canonical link as well as OpenGraph and Twitter card meta tags.
+struct PostMeta<'a> {
+    site_name: &'a str,
+    canonical_url: String,
+    description: String,
+    image: Option<String>,
+}
+
+impl<'a> PostMeta<'a> {
+    fn new(site: &'a SiteSettings, post: &BlogPost) -> Self {
+        let url = post.url();
+        Self {
+            site_name: &site.title,
+            canonical_url: site.absolute_url(&url),
+            description: post.description(&site.markdown),
+            image: post
+                .cover_image(&site.markdown)
+                .map(|image| site.resolve_url(&url, &image)),
+        }
+    }
+}
+
+struct SeriesNavigation<'a> {
+    series: &'a Series,
+    part: usize,
+    previous: Option<&'a BlogPost>,
+    next: Option<&'a BlogPost>,
+}
+
+impl<'a> SeriesNavigation<'a> {
+    fn new(series: &'a Series, post: &BlogPost) -> Option<Self> {
+        let index = series.position(post.id)?;
+        Some(Self {
+            series,
+            part: index + 1,
+            previous: index.checked_sub(1).and_then(|i| series.posts.get(i)),
+            next: series.posts.get(index + 1),
+        })
+    }
+
+    fn url(&self) -> String {
+        format!("/series/{}", self.series.url_id)
+    }
+}
+
+#[tracing::instrument(name = "Viewing post", skip(reader_uc, admin_uc, session, site))]
 pub async fn view_post_page(
     reader_uc: web::Data<ReaderUseCase>,
+    admin_uc: web::Data<AdminUseCase>,
+    session: TypedSession,
+    site: web::Data<SiteSettings>,
     id: web::Path<String>,
 ) -> Result<HttpResponse, WebError> {
-    if let Some(post) = reader_uc.get_post_by_url_id(&id.into_inner()).await? {
-        Ok(PostTemplate {
-            title: &post.title,
-            post: &post,
-        }
-        .to_response())
+    let id = id.into_inner();
+    // The admin can view posts that are not visible to readers, e.g. drafts.
+    let post = if session.is_admin().unwrap_or(false) {
+        admin_uc.get_post_by_url_id(&id).await?
     } else {
-        Ok(HttpResponse::new(StatusCode::NOT_FOUND))
+        reader_uc.get_post_by_url_id(&id).await?
+    };
+
+    let Some(post) = post else {
+        return Ok(HttpResponse::new(StatusCode::NOT_FOUND));
+    };
+
+    let series = match &post.series {
+        Some(s) => reader_uc.get_series(&s.url_id).await?,
+        None => None,
+    };
+
+    Ok(PostTemplate {
+        title: &post.title,
+        post: &post,
+        series: series
+            .as_ref()
+            .and_then(|s| SeriesNavigation::new(s, &post)),
+        meta: PostMeta::new(&site, &post),
+        toc: post.toc.as_deref(),
     }
+    .to_response())
 }
@@ -0,0 +1,71 @@
+use domain::MarkdownSettings;
+
+/// Site wide settings that pages need when rendering.
+#[derive(Debug, Clone)]
+pub struct SiteSettings {
+    pub title: String,
+    /// Used to create absolute URLs, e.g. in feeds.
+    pub base_url: String,
+    /// Served as is instead of the default robots.txt if set.
+    pub robots_txt: Option<String>,
+    /// The settings the posts are rendered with, e.g. to take descriptions
+    /// from them the same way.
+    pub markdown: MarkdownSettings,
+}
+
+impl SiteSettings {
+    pub fn absolute_url(&self, path: &str) -> String {
+        format!(
+            "{}/{}",
+            self.base_url.trim_end_matches('/'),
+            path.trim_start_matches('/')
+        )
+    }
+
+    /// Resolves a URL found on the page at `page_path` the way a browser
+    /// would, e.g. `a.png` on `/blog/post` becomes `<base_url>/blog/a.png`.
+    /// URLs with a scheme are returned as is.
+    pub fn resolve_url(&self, page_path: &str, url: &str) -> String {
+        let has_scheme = url.split_once(':').is_some_and(|(scheme, _)| {
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        });
+        if has_scheme {
+            url.to_string()
+        } else if let Some(host_and_path) = url.strip_prefix("//") {
+            let scheme = self.base_url.split_once("://").map_or("https", |(s, _)| s);
+            format!("{scheme}://{host_and_path}")
+        } else if url.starts_with('/') {
+            self.absolute_url(url)
+        } else if url.is_empty() || url.starts_with(['#', '?']) {
+            self.absolute_url(&format!("{page_path}{url}"))
+        } else {
+            let directory = page_path.rfind('/').map_or("/", |i| &page_path[..=i]);
+            self.absolute_url(&format!("{directory}{url}"))
+        }
+    }
+
+    /// Resolves the `href` and `src` attributes in `html` rendered for the
+    /// page at `page_path`, for HTML that is shown somewhere else, e.g. in
+    /// feed readers.
+    pub fn resolve_urls_in_html(&self, page_path: &str, html: &str) -> String {
+        let mut resolved = String::with_capacity(html.len());
+        let mut rest = html;
+        while let Some(start) = [" href=\"", " src=\""]
+            .iter()
+            .filter_map(|attribute| rest.find(attribute).map(|i| i + attribute.len()))
+            .min()
+        {
+            let Some(end) = rest[start..].find('"').map(|i| start + i) else {
+                break;
+            };
+            resolved.push_str(&rest[..start]);
+            resolved.push_str(&self.resolve_url(page_path, &rest[start..end]));
+            rest = &rest[end..];
+        }
+        resolved.push_str(rest);
+        resolved
+    }
+}
@@ -1,12 +1,28 @@
 use crate::api::admin_login::admin_login;
+use crate::api::edit_post::edit_post;
+use crate::api::feed::{atom_feed, json_feed, rss_feed, tag_atom_feed, tag_rss_feed};
 use crate::api::health_check::health_check;
 use crate::api::new_post::{blow_up, new_post, preview_html};
+use crate::api::post_status::{
+    pin_post, publish_post, purge_post, rerender_posts, restore_post, trash_post, unpin_post,
+    unpublish_post,
+};
 use crate::api::r#static::{css_base, css_dark, css_light};
-use crate::pages::admin_draft_page::draft_post_page;
+use crate::api::revisions::restore_revision;
+use crate::api::sitemap::{robots_txt, sitemap};
+use crate::pages::admin_draft_page::{draft_post_page, edit_post_page};
+use crate::pages::admin_posts_page::admin_posts_page;
+use crate::pages::admin_revisions_page::{revision_diff_page, revisions_page};
+use crate::pages::admin_trash_page::trash_page;
+use crate::pages::archive_page::{archive_month_page, archive_page, archive_year_page};
 use crate::pages::index_page::blog_posts_page;
 use crate::pages::login_page::login_page;
+use crate::pages::search_page::search_page;
+use crate::pages::series_page::series_page;
+use crate::pages::tags_page::{tag_page, tags_page};
 use crate::pages::view_post_page::view_post_page;
 use crate::session::TypedSession;
+use crate::site::SiteSettings;
 use actix_session::config::CookieContentSecurity;
 use actix_session::storage::CookieSessionStore;
 use actix_session::{SessionExt, SessionMiddleware};
@@ -19,7 +35,7 @@ use actix_web::web::Data;
 use actix_web::{web, App, HttpResponse, HttpServer, Scope};
 use actix_web_flash_messages::storage::CookieMessageStore;
 use actix_web_flash_messages::FlashMessagesFramework;
-use domain::{AdminUseCase, HmacSecret, ReaderUseCase};
+use domain::{AdminUseCase, HmacSecret, ReaderUseCase, SyntaxHighlighting};
 use secrecy::ExposeSecret;
 use std::ffi::OsStr;
 use std::future;
@@ -30,7 +46,9 @@ pub struct ServerArguments {
     pub listener: TcpListener,
     pub reader_use_case: Data<ReaderUseCase>,
     pub admin_use_case: Data<AdminUseCase>,
+    pub highlighting: Data<SyntaxHighlighting>,
     pub hmac_secret: HmacSecret,
+    pub site: SiteSettings,
 }
 
 pub fn run_server(
@@ -38,9 +56,12 @@ pub fn run_server(
         listener,
         reader_use_case,
         admin_use_case,
+        highlighting,
         hmac_secret,
+        site,
     }: ServerArguments,
 ) -> Result<Server, std::io::Error> {
+    let site = Data::new(site);
     let server = HttpServer::new(move || {
         let key = Key::from(hmac_secret.expose_secret().as_bytes());
 
@@ -62,6 +83,20 @@ pub fn run_server(
             .route("login", web::post().to(admin_login))
             .route("/health_check", web::get().to(health_check))
             .service(web::resource("/blog/{post}").route(web::get().to(view_post_page)))
+            .route("/search", web::get().to(search_page))
+            .route("/series/{series}", web::get().to(series_page))
+            .route("/archive", web::get().to(archive_page))
+            .route("/archive/{year}", web::get().to(archive_year_page))
+            .route("/archive/{year}/{month}", web::get().to(archive_month_page))
+            .route("/tags", web::get().to(tags_page))
+            .route("/tags/{tag}", web::get().to(tag_page))
+            .route("/tags/{tag}/feed.atom", web::get().to(tag_atom_feed))
+            .route("/tags/{tag}/feed.rss", web::get().to(tag_rss_feed))
+            .route("/feed.atom", web::get().to(atom_feed))
+            .route("/feed.rss", web::get().to(rss_feed))
+            .route("/feed.json", web::get().to(json_feed))
+            .route("/sitemap.xml", web::get().to(sitemap))
+            .route("/robots.txt", web::get().to(robots_txt))
             .service(
                 web::scope("static")
                     .route("light.css", web::get().to(css_light))
@@ -71,11 +106,34 @@ pub fn run_server(
             .service(
                 authorized_scope("admin")
                     .route("draft", web::get().to(draft_post_page))
+                    .route("posts", web::get().to(admin_posts_page))
                     .route("new_post", web::post().to(new_post))
+                    .route("edit/{post}", web::get().to(edit_post_page))
+                    .route("edit/{post}", web::post().to(edit_post))
+                    .route("trash", web::get().to(trash_page))
+                    .route("posts/{post}/publish", web::post().to(publish_post))
+                    .route("posts/{post}/unpublish", web::post().to(unpublish_post))
+                    .route("posts/rerender", web::post().to(rerender_posts))
+                    .route("posts/{post}/pin", web::post().to(pin_post))
+                    .route("posts/{post}/unpin", web::post().to(unpin_post))
+                    .route("posts/{post}/trash", web::post().to(trash_post))
+                    .route("posts/{post}/restore", web::post().to(restore_post))
+                    .route("posts/{post}/purge", web::post().to(purge_post))
+                    .route("posts/{post}/revisions", web::get().to(revisions_page))
+                    .route(
+                        "posts/{post}/revisions/{revision}",
+                        web::get().to(revision_diff_page),
+                    )
+                    .route(
+                        "posts/{post}/revisions/{revision}/restore",
+                        web::post().to(restore_revision),
+                    )
                     .route("preview_html", web::post().to(preview_html)),
             )
             .app_data(reader_use_case.clone())
-            .app_data(admin_use_case.clone());
+            .app_data(admin_use_case.clone())
+            .app_data(highlighting.clone())
+            .app_data(site.clone());
 
         if is_running_integration_test_or_benchmark() {
             app = app.route("/blow_up", web::get().to(blow_up));
@@ -103,7 +161,7 @@ fn authorized_scope(
     web::scope(scope).wrap_fn(|req, service| {
         let session: TypedSession = req.get_session().into();
 
-        return match session.is_admin() {
+        match session.is_admin() {
             Ok(true) => service.call(req),
             Ok(false) => Box::pin(future::ready(Ok(ServiceResponse::new(
                 req.request().clone(),
@@ -113,7 +171,7 @@ fn authorized_scope(
                 req.request().clone(),
                 HttpResponse::from_error(ErrorInternalServerError(err)),
             )))),
-        };
+        }
     })
 }
 
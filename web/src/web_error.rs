@@ -12,6 +12,9 @@ impl ResponseError for WebError {
         match self.0 {
             UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AdminAuthError => StatusCode::UNAUTHORIZED,
+            PostNotFound(_) | RevisionNotFound(_) | PageNotFound(_) | TagNotFound(_) => {
+                StatusCode::NOT_FOUND
+            }
             _ => StatusCode::BAD_REQUEST,
         }
     }